@@ -4,49 +4,327 @@ use std::collections::HashMap;
 use std::env;
 use std::fs;
 mod schemer;
-use schemer::objects::{Module};
+use schemer::objects::{Module, FieldType};
 use schemer::lexer::{Lexer};
 use schemer::parser::Parser;
 use schemer::to_schemer::{module_to_string};
-use schemer::to_json::{to_json_schema, to_json_values};
+use schemer::json::JsonValue;
+use schemer::from_json_schema::from_json_schema;
+use schemer::infer::infer_module;
+use schemer::validate::{validate_module, validate_field, validate_overrides, validate_merged};
+use schemer::overrides::{apply_overrides, apply_profile};
+use schemer::to_json::{to_json_schema, to_json_values, field_to_json_schema, field_to_json_values, json_schema_warnings};
+use schemer::formatting::{Formatting, SecretMode, UnitFormat, SchemaDraft};
 
-fn show_in_json_value(value: &Module, shift: usize, _: &str) {
-    println!("{}", to_json_values(value, shift))
+type OutputCall = dyn Fn(&Module, Option<&FieldType>, &Formatting) -> String;
+
+// a root field is written on its own, without the module around it
+fn show_in_json_value(value: &Module, root: Option<&FieldType>, format: &Formatting) -> String {
+    match root {
+        Some(fld) => field_to_json_values(fld, format),
+        None => to_json_values(value, format),
+    }
+}
+
+fn show_in_json_schema(value: &Module, root: Option<&FieldType>, format: &Formatting) -> String {
+    let fields = match root {
+        Some(fld) => std::slice::from_ref(fld),
+        None => value.fields(),
+    };
+    for warning in json_schema_warnings(fields, format) {
+        eprintln!("Schema warning: {}", warning);
+    }
+    match root {
+        Some(fld) => field_to_json_schema(value, fld, format),
+        None => to_json_schema(value, format),
+    }
+}
+
+fn show_in_schemer(value: &Module, root: Option<&FieldType>, format: &Formatting) -> String {
+    match root.and_then(|fld| value.only_field(fld.name())) {
+        Some(module) => module_to_string(&module, format),
+        None => module_to_string(value, format),
+    }
+}
+
+fn file_extension(call_name: &str) -> &'static str {
+    match call_name {
+        "json_value" | "values" => "json",
+        "json_schema" => "schema.json",
+        _ => "schemer",
+    }
+}
+
+// one file per top-level field: <dir>/<field>.<extension>
+fn write_all_roots(module: &Module, call: &OutputCall, format: &Formatting, dir: &str, extension: &str) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|err| format!("creating directory {} error. {}", dir, err))?;
+    for fld in module.fields() {
+        let path = std::path::Path::new(dir).join(format!("{}.{}", fld.name(), extension));
+        let mut text = call(module, Some(fld), format);
+        text.push('\n');
+        fs::write(&path, text).map_err(|err| format!("writing file {} error. {}", path.display(), err))?;
+    }
+    Ok(())
+}
+
+// flags that can be given without a value
+const BOOL_FLAGS: &[&str] = &["check-paths"];
+
+// splits arguments into positional ones and flags like `--name=value` or `--name value`
+fn split_args(args: &[String]) -> Result<(Vec<String>, HashMap<String, String>), String> {
+    let mut positional = Vec::new();
+    let mut flags = HashMap::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.strip_prefix("--") {
+            Some(flag) => match flag.find('=') {
+                Some(pos) => { 
+                    flags.insert(flag[..pos].to_string(), flag[pos + 1..].to_string()); 
+                },
+                // `--check-paths` is the same as `--check-paths=true`
+                None if BOOL_FLAGS.contains(&flag) => {
+                    flags.insert(flag.to_string(), String::from("true"));
+                },
+                None => match iter.next() {
+                    Some(value) => { flags.insert(flag.to_string(), value.to_string()); },
+                    None => return Err(format!("Flag '{}' requires a value", arg)),
+                },
+            },
+            None => positional.push(arg.to_string()),
+        }
+    }
+    Ok((positional, flags))
+}
+
+fn create_format(shift: usize, flags: &HashMap<String, String>) -> Result<Formatting, String> {
+    let mut format = Formatting::new(shift);
+    if let Some(name) = flags.get("secrets") {
+        match SecretMode::from_name(name) {
+            Some(mode) => format = format.with_secrets(mode),
+            None => return Err(format!("Invalid secrets mode '{}'. Use redact, omit or show", name)),
+        }
+    }
+    if let Some(name) = flags.get("units") {
+        match UnitFormat::from_name(name) {
+            Some(units) => format = format.with_units(units),
+            None => return Err(format!("Invalid units format '{}'. Use string or integer", name)),
+        }
+    }
+    if let Some(name) = flags.get("draft") {
+        match SchemaDraft::from_name(name) {
+            Some(draft) => format = format.with_draft(draft),
+            None => return Err(format!("Invalid schema draft '{}'. Use draft-04, draft-07, 2019-09 or 2020-12", name)),
+        }
+    }
+    match flags.get("sort").map(|v| v.as_str()) {
+        None | Some("declared") => {},
+        Some("alpha") => format = format.with_sorted_keys(true),
+        Some(name) => return Err(format!("Invalid sort mode '{}'. Use declared or alpha", name)),
+    }
+    Ok(format)
 }
 
-fn show_in_json_schema(value: &Module, shift: usize, _: &str) {
-    println!("{}", to_json_schema(value, shift));
+fn flag_bool(flags: &HashMap<String, String>, name: &str) -> Result<bool, String> {
+    match flags.get(name).map(|v| v.as_str()) {
+        None | Some("false") => Ok(false),
+        Some("true") => Ok(true),
+        Some(val) => Err(format!("Invalid value '{}' for '--{}'. Use true or false", val, name)),
+    }
 }
 
-fn show_in_schemer(value: &Module, shift: usize, _: &str) {
-    println!("{}", module_to_string(value, shift));
+// where the output goes: stdout for the whole module or one root, files for all the roots
+enum Target<'a> {
+    Module,
+    Root(&'a str),
+    AllRoots(&'a str, &'static str),
 }
 
-fn parse_format(obj: &str, call: &'static dyn Fn(&Module, usize, &str), shift: usize, root_name: &str) {
+fn read_module(obj: &str, check_paths: bool) -> Option<Module> {
     let lex = Lexer::new();
     let vec = lex.run(obj);
-    let mut pars = Parser::new(Vec::new());
-    match vec {
-        Err(expr) => eprintln!("Parsing error: {}", expr),
-        Ok(v) => {
-            pars = Parser::new(v);
+    let mut pars = match vec {
+        Err(expr) => {
+            eprintln!("Parsing error: {}", expr);
+            return None;
         },
+        Ok(v) => Parser::new(v),
     };
+    pars.set_check_paths(check_paths);
     
-    match &pars.parse_module() {
-        Ok(val) => call(val, shift, root_name),
-        Err(err) => println!("Parser error: {}", err.msg()),
+    let module = pars.parse_module();
+    for warning in pars.warnings() {
+        eprintln!("Parser warning: {}", warning);
+    }
+    match module {
+        Ok(val) => Some(val),
+        Err(err) => {
+            println!("Parser error: {}", err.msg());
+            None
+        },
+    }
+}
+
+// a json schema document converted to a module
+fn import_module(obj: &str) -> Option<Module> {
+    match JsonValue::parse(obj) {
+        Ok(schema) => {
+            let (module, warnings) = from_json_schema(&schema);
+            for warning in warnings {
+                eprintln!("Import warning: {}", warning);
+            }
+            Some(module)
+        },
+        Err(err) => {
+            eprintln!("Json error: {}", err);
+            None
+        },
+    }
+}
+
+// the first sample with the ones from `--samples=a.json,b.json`
+fn infer_from_samples(obj: &str, flags: &HashMap<String, String>) -> Option<Module> {
+    let mut texts = vec![(String::from("the first sample"), obj.to_string())];
+    for path in flags.get("samples").map(|v| v.split(',').collect::<Vec<&str>>()).unwrap_or_default() {
+        match fs::read_to_string(path) {
+            Ok(text) => texts.push((path.to_string(), text)),
+            Err(err) => {
+                eprintln!("reading file {} error. {}", path, err);
+                return None;
+            },
+        }
+    }
+    let mut samples = Vec::new();
+    for (name, text) in texts {
+        match JsonValue::parse(&text) {
+            Ok(val) => samples.push(val),
+            Err(err) => {
+                eprintln!("Json error in {}: {}", name, err);
+                return None;
+            },
+        }
+    }
+    Some(infer_module(&samples))
+}
+
+// checks the document from `--instance=config.json`, every violation is printed
+fn validate_instance(val: &Module, flags: &HashMap<String, String>, target: &Target, check_paths: bool) -> bool {
+    let path = match flags.get("instance") {
+        Some(v) => v,
+        None => {
+            eprintln!("'validate' needs a document to check: --instance=config.json");
+            return false;
+        },
+    };
+    let instance = match fs::read_to_string(path).map_err(|e| e.to_string()).and_then(|text| JsonValue::parse(&text)) {
+        Ok(v) => v,
+        Err(err) => {
+            eprintln!("Json error in {}: {}", path, err);
+            return false;
+        },
+    };
+    let violations = match target {
+        Target::Root(name) => match val.get_field(name) {
+            Some(fld) => validate_field(fld, &instance, check_paths),
+            None => {
+                let names = val.fields().iter().map(|f| f.name()).collect::<Vec<&str>>();
+                eprintln!("Root '{}' is not found. Top-level fields: {}", name, names.join(", "));
+                return false;
+            },
+        },
+        _ => validate_module(val, &instance, check_paths),
+    };
+    for violation in &violations {
+        println!("{}", violation);
+    }
+    if violations.is_empty() {
+        println!("{} is valid", path);
+    }
+    violations.is_empty()
+}
+
+// the defaults with the values of `--profile=prod`
+fn select_profile(val: Module, flags: &HashMap<String, String>) -> Option<Module> {
+    match flags.get("profile") {
+        Some(name) => match apply_profile(&val, name) {
+            Ok(res) => Some(res),
+            Err(err) => {
+                eprintln!("{}", err);
+                None
+            },
+        },
+        None => Some(val),
+    }
+}
+
+// the defaults with the values from `--override=local.json`, every rejected value is printed
+fn override_module(val: Module, flags: &HashMap<String, String>, check_paths: bool) -> Option<Module> {
+    let path = match flags.get("override") {
+        Some(v) => v,
+        None => return Some(val),
     };
+    let overrides = match fs::read_to_string(path).map_err(|e| e.to_string()).and_then(|text| JsonValue::parse(&text)) {
+        Ok(v) => v,
+        Err(err) => {
+            eprintln!("Json error in {}: {}", path, err);
+            return None;
+        },
+    };
+    let violations = validate_overrides(&val, &overrides, check_paths);
+    if !violations.is_empty() {
+        for violation in &violations {
+            eprintln!("{}", violation);
+        }
+        return None;
+    }
+    let merged = apply_overrides(&val, &overrides);
+    let violations = validate_merged(&val, &merged, &overrides);
+    if !violations.is_empty() {
+        for violation in &violations {
+            eprintln!("{}", violation);
+        }
+        return None;
+    }
+    Some(merged)
+}
+
+fn write_module(val: &Module, call: &OutputCall, format: &Formatting, target: &Target) {
+    match target {
+        Target::Module => println!("{}", call(val, None, format)),
+        Target::Root(name) => match val.get_field(name) {
+            Some(fld) => println!("{}", call(val, Some(fld), format)),
+            None => {
+                let names = val.fields().iter().map(|f| f.name()).collect::<Vec<&str>>();
+                eprintln!("Root '{}' is not found. Top-level fields: {}", name, names.join(", "));
+                std::process::exit(1);
+            },
+        },
+        Target::AllRoots(dir, extension) => {
+            if let Err(err) = write_all_roots(val, call, format, dir, extension) {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            }
+        },
+    }
 }
 
 fn main() {
 
-    let mut calls: HashMap<String, &'static dyn Fn(&Module, usize, &str)> = HashMap::new();
+    let mut calls: HashMap<String, &'static OutputCall> = HashMap::new();
     calls.insert("json_value".to_string(), &show_in_json_value);
     calls.insert("json_schema".to_string(), &show_in_json_schema);
     calls.insert("schemer".to_string(), &show_in_schemer);
+    calls.insert("import".to_string(), &show_in_schemer);
+    calls.insert("infer".to_string(), &show_in_schemer);
+    calls.insert("validate".to_string(), &show_in_schemer);
+    calls.insert("values".to_string(), &show_in_json_value);
 
-    let args: Vec<String> = env::args().collect();
+    let (args, flags) = match split_args(&env::args().collect::<Vec<String>>()) {
+        Ok(val) => val,
+        Err(err) => {
+            eprintln!("{}", err);
+            return;
+        },
+    };
     if args.len() >= 2 {
         let call_name = if args.len() > 2 { &args[2] } else { "schemer" };
         let call = match calls.get(call_name) {
@@ -54,7 +332,8 @@ fn main() {
             None => &show_in_schemer,
         };
         let mut def_shift: usize = 2;
-        let mut root_name = "main";
+        // `-` is the whole module, so the shift can be set without a root
+        let mut root_name = "-";
         if args.len() >= 4 {
             root_name = &args[3];
             if args.len() >= 5 {
@@ -62,15 +341,67 @@ fn main() {
                   Ok(val) => val,
                   Err(err) => {
                       eprintln!("Invalid shift value '{}'. Error: {}", args[4], err);
-                      return ();
+                      return;
                   },
               }
             }
         }
+        let format = match create_format(def_shift, &flags) {
+            Ok(val) => val,
+            Err(err) => {
+                eprintln!("{}", err);
+                return;
+            },
+        };
+        let check_paths = match flag_bool(&flags, "check-paths") {
+            Ok(val) => val,
+            Err(err) => {
+                eprintln!("{}", err);
+                return;
+            },
+        };
+        let target = match (flags.get("all-roots"), root_name) {
+            (Some(_), _) if call_name == "validate" => {
+                eprintln!("'--all-roots' can't be used with 'validate'");
+                return;
+            },
+            (Some(dir), "-") => Target::AllRoots(dir, file_extension(call_name)),
+            (Some(_), _) => {
+                eprintln!("'--all-roots' writes every top-level field, it can't be used with a root name");
+                return;
+            },
+            (None, "-") => Target::Module,
+            (None, name) => Target::Root(name),
+        };
+        // profiles change the default values, other outputs keep the profile blocks as they are
+        let with_profile = flags.contains_key("profile");
+        if with_profile && call_name != "json_value" && call_name != "values" {
+            eprintln!("'--profile' can be used only with 'json_value' and 'values'");
+            std::process::exit(1);
+        }
         let test_object = fs::read_to_string(&args[1]);
         match &test_object {
             Ok(obj) => {
-                parse_format(obj, call, def_shift, root_name);
+                let module = match call_name {
+                    "import" => import_module(obj),
+                    "infer" => infer_from_samples(obj, &flags),
+                    "values" => read_module(obj, check_paths)
+                        .and_then(|val| select_profile(val, &flags))
+                        .and_then(|val| override_module(val, &flags, check_paths)),
+                    "json_value" => read_module(obj, check_paths).and_then(|val| select_profile(val, &flags)),
+                    _ => read_module(obj, check_paths),
+                };
+                match module {
+                    Some(val) if call_name == "validate" => {
+                        let valid = validate_instance(&val, &flags, &target, check_paths);
+                        if !valid {
+                            std::process::exit(1);
+                        }
+                    },
+                    Some(val) => write_module(&val, call, &format, &target),
+                    None if call_name == "validate" || call_name == "values" || with_profile => std::process::exit(1),
+                    None => {},
+                }
             },
             Err(err) => {
                 eprintln!("reading file {} error. {}", args[1], err);
@@ -108,7 +439,7 @@ fn main() {
         };
         match &pars.parse_module() {
             Ok(val) => {
-                println!("mod: {}", module_to_string(val, 2));
+                println!("mod: {}", module_to_string(val, &Formatting::new(2)));
             }
             Err(err) => println!("Parser error: {}", err.msg()),
         };
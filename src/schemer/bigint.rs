@@ -0,0 +1,121 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use super::helpers::Numeric;
+
+// an integer of any size kept as decimal digits, for ids and hashes
+#[derive(Clone)]
+pub struct BigInt {
+    negative: bool,
+    digits: String, // without leading zeros, "0" for zero
+}
+
+impl BigInt {
+    pub fn from_decimal(data: &str, negative: bool) -> Option<BigInt> {
+        if data.is_empty() || !data.bytes().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let digits = data.trim_start_matches('0');
+        let digits = if digits.is_empty() { "0" } else { digits };
+        Some(BigInt {
+            negative: negative && digits != "0",
+            digits: digits.to_string(),
+        })
+    }
+
+    // digits of a number in the base (2..=36), the most significant first
+    pub fn from_digits(digits: &[u32], base: u32) -> BigInt {
+        // little endian limbs of 10^9
+        let mut limbs: Vec<u64> = vec![0];
+        for d in digits {
+            let mut carry = *d as u64;
+            for limb in limbs.iter_mut() {
+                let val = *limb * base as u64 + carry;
+                *limb = val % 1_000_000_000;
+                carry = val / 1_000_000_000;
+            }
+            while carry > 0 {
+                limbs.push(carry % 1_000_000_000);
+                carry /= 1_000_000_000;
+            }
+        }
+        let mut text = limbs.last().map(|v| v.to_string()).unwrap_or_default();
+        for limb in limbs.iter().rev().skip(1) {
+            text.push_str(&format!("{:09}", limb));
+        }
+        BigInt { negative: false, digits: text }
+    }
+
+    pub fn negate(mut self) -> BigInt {
+        self.negative = !self.negative && self.digits != "0";
+        self
+    }
+
+    pub fn digits(&self) -> &str {
+        &self.digits
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        let val = self.digits.parse::<i128>().ok()?;
+        i64::try_from(if self.negative { -val } else { val }).ok()
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        if self.negative {
+            None
+        } else {
+            self.digits.parse::<u64>().ok()
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse::<f64>().unwrap_or(0.0)
+    }
+
+    fn compare_abs(&self, other: &BigInt) -> Ordering {
+        self.digits.len().cmp(&other.digits.len()).then_with(|| self.digits.cmp(&other.digits))
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(val: i64) -> Self {
+        BigInt {
+            negative: val < 0,
+            digits: val.unsigned_abs().to_string(),
+        }
+    }
+}
+
+impl PartialEq for BigInt {
+    fn eq(&self, other: &Self) -> bool {
+        self.negative == other.negative && self.digits == other.digits
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => self.compare_abs(other),
+            (true, true) => other.compare_abs(self),
+        })
+    }
+}
+
+impl std::fmt::Display for BigInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", if self.negative { "-" } else { "" }, self.digits)
+    }
+}
+
+impl Numeric for BigInt {
+    fn zero() -> Self {
+        BigInt { negative: false, digits: String::from("0") }
+    }
+    fn name() -> &'static str {
+        "bigint"
+    }
+    fn is_multiple_of(&self, _: &Self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,288 @@
+use super::helpers::Numeric;
+
+// RFC 3339 times with an optional offset, the `time` format requires one
+pub const TIME_PATTERN: &str = "^([01][0-9]|2[0-3]):[0-5][0-9]:([0-5][0-9]|60)([.][0-9]+)?([Zz]|[+-]([01][0-9]|2[0-3]):[0-5][0-9])?$";
+
+mod utils {
+    pub fn digits(data: &[u8], at: usize, count: usize) -> bool {
+        at + count <= data.len() && data[at..at + count].iter().all(|c| c.is_ascii_digit())
+    }
+
+    pub fn number(data: &str, from: usize, to: usize) -> u32 {
+        data[from..to].parse::<u32>().unwrap_or(0)
+    }
+
+    // end of hh:mm:ss[.frac][Z|+hh:mm] started at `at`, or 0
+    pub fn time_end(data: &[u8], at: usize) -> usize {
+        if !(digits(data, at, 2) && data.get(at + 2) == Some(&b':')
+            && digits(data, at + 3, 2) && data.get(at + 5) == Some(&b':')
+            && digits(data, at + 6, 2)) {
+            return 0;
+        }
+        let mut end = at + 8;
+        if data.get(end) == Some(&b'.') && digits(data, end + 1, 1) {
+            end += 1;
+            while digits(data, end, 1) {
+                end += 1;
+            }
+        }
+        match data.get(end) {
+            Some(b'Z') | Some(b'z') => end + 1,
+            Some(b'+') | Some(b'-') if digits(data, end + 1, 2)
+                && data.get(end + 3) == Some(&b':')
+                && digits(data, end + 4, 2) => end + 6,
+            _ => end,
+        }
+    }
+
+    pub fn is_leap(year: u32) -> bool {
+        (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+    }
+
+    pub fn days_in_month(year: u32, month: u32) -> u32 {
+        match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 => if is_leap(year) { 29 } else { 28 },
+            _ => 0,
+        }
+    }
+
+    // days since 1970-01-01
+    pub fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+        let y = if month <= 2 { year - 1 } else { year };
+        let era = if y >= 0 { y } else { y - 399 } / 400;
+        let yoe = y - era * 400;
+        let mp = (month + 9) % 12;
+        let doy = (153 * mp + 2) / 5 + day - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146097 + doe - 719468
+    }
+}
+
+// length of a date (2020-01-01), time (12:30:00) or date-time (2020-01-01T12:30:00Z) literal
+// at the beginning of the string, 0 if there is no literal
+pub fn literal_len(data: &str) -> usize {
+    use utils::digits;
+    let bytes = data.as_bytes();
+    if digits(bytes, 0, 4) && bytes.get(4) == Some(&b'-')
+        && digits(bytes, 5, 2) && bytes.get(7) == Some(&b'-')
+        && digits(bytes, 8, 2) {
+        if let Some(b'T') | Some(b't') = bytes.get(10) {
+            let end = utils::time_end(bytes, 11);
+            if end > 0 {
+                return end;
+            }
+        }
+        return 10;
+    }
+    utils::time_end(bytes, 0)
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+pub struct Date {
+    year: u32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    pub fn parse(data: &str) -> Result<Date, String> {
+        if data.len() != 10 || literal_len(data) != 10 {
+            return Err(format!("Invalid date '{}'. Expected YYYY-MM-DD", data));
+        }
+        let year = utils::number(data, 0, 4);
+        let month = utils::number(data, 5, 7);
+        let day = utils::number(data, 8, 10);
+        if !(1..=12).contains(&month) {
+            return Err(format!("Invalid date '{}': the month is out of range", data));
+        }
+        let days = utils::days_in_month(year, month);
+        if day < 1 || day > days {
+            return Err(format!("Invalid date '{}': the day is out of range, {:04}-{:02} has {} days", data, year, month, days));
+        }
+        Ok(Date { year, month, day })
+    }
+
+    fn days(&self) -> i64 {
+        utils::days_from_civil(self.year as i64, self.month as i64, self.day as i64)
+    }
+}
+
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Numeric for Date {
+    fn zero() -> Self {
+        Date { year: 1970, month: 1, day: 1 }
+    }
+    fn name() -> &'static str {
+        "date"
+    }
+    fn is_multiple_of(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Time {
+    hour: u32,
+    minute: u32,
+    second: u32,
+    nanos: u32,
+    offset: Option<i32>, // minutes
+}
+
+impl Time {
+    pub fn parse(data: &str) -> Result<Time, String> {
+        if data.is_empty() || utils::time_end(data.as_bytes(), 0) != data.len() {
+            return Err(format!("Invalid time '{}'. Expected hh:mm:ss[.frac][Z|+hh:mm]", data));
+        }
+        let hour = utils::number(data, 0, 2);
+        let minute = utils::number(data, 3, 5);
+        // 60 is a leap second
+        let second = utils::number(data, 6, 8);
+        let rest = &data[8..];
+        let offset_pos = rest.find(['Z', 'z', '+', '-']).unwrap_or(rest.len());
+        let nanos = if rest.starts_with('.') {
+            let frac = &rest[1..offset_pos];
+            let digits = if frac.len() > 9 { &frac[..9] } else { frac };
+            utils::number(digits, 0, digits.len()) * 10_u32.pow(9 - digits.len() as u32)
+        } else {
+            0
+        };
+        let offset = match &rest[offset_pos..] {
+            "" => None,
+            "Z" | "z" => Some(0),
+            zone => {
+                let hours = utils::number(zone, 1, 3);
+                let minutes = utils::number(zone, 4, 6);
+                if hours > 23 || minutes > 59 {
+                    return Err(format!("Invalid time offset in '{}'", data));
+                }
+                let value = (hours * 60 + minutes) as i32;
+                Some(if zone.starts_with('-') { -value } else { value })
+            },
+        };
+        if hour > 23 || minute > 59 || second > 60 {
+            return Err(format!("Invalid time '{}'", data));
+        }
+        Ok(Time { hour, minute, second, nanos, offset })
+    }
+
+    pub fn has_offset(&self) -> bool {
+        self.offset.is_some()
+    }
+
+    // seconds from the midnight in UTC, times without offset are treated as UTC
+    fn seconds(&self) -> i64 {
+        (self.hour * 3600 + self.minute * 60 + self.second) as i64
+            - self.offset.unwrap_or(0) as i64 * 60
+    }
+}
+
+impl PartialEq for Time {
+    fn eq(&self, other: &Self) -> bool {
+        (self.seconds(), self.nanos) == (other.seconds(), other.nanos)
+    }
+}
+
+impl PartialOrd for Time {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        (self.seconds(), self.nanos).partial_cmp(&(other.seconds(), other.nanos))
+    }
+}
+
+impl std::fmt::Display for Time {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+        if self.nanos > 0 {
+            let frac = format!("{:09}", self.nanos);
+            write!(f, ".{}", frac.trim_end_matches('0'))?;
+        }
+        match self.offset {
+            None => Ok(()),
+            Some(0) => write!(f, "Z"),
+            Some(val) => write!(f, "{}{:02}:{:02}",
+                if val < 0 { '-' } else { '+' }, val.abs() / 60, val.abs() % 60),
+        }
+    }
+}
+
+impl Numeric for Time {
+    fn zero() -> Self {
+        Time { hour: 0, minute: 0, second: 0, nanos: 0, offset: None }
+    }
+    fn name() -> &'static str {
+        "time"
+    }
+    fn is_multiple_of(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct DateTime {
+    date: Date,
+    time: Time,
+}
+
+impl DateTime {
+    // RFC 3339 date-time requires an offset
+    pub fn parse(data: &str) -> Result<DateTime, String> {
+        let sep = match data.find(['T', 't']) {
+            Some(pos) => pos,
+            None => return Err(format!("Invalid date-time '{}'. Expected YYYY-MM-DDThh:mm:ssZ", data)),
+        };
+        let date = Date::parse(&data[..sep])?;
+        let time = Time::parse(&data[sep + 1..])?;
+        if !time.has_offset() {
+            return Err(format!("Date-time '{}' should have an offset (Z or +hh:mm)", data));
+        }
+        Ok(DateTime { date, time })
+    }
+
+    // midnight in UTC
+    pub fn from_date(date: Date) -> DateTime {
+        let mut time = Time::zero();
+        time.offset = Some(0);
+        DateTime { date, time }
+    }
+
+    fn seconds(&self) -> i64 {
+        self.date.days() * 86400 + self.time.seconds()
+    }
+}
+
+impl PartialEq for DateTime {
+    fn eq(&self, other: &Self) -> bool {
+        (self.seconds(), self.time.nanos) == (other.seconds(), other.time.nanos)
+    }
+}
+
+impl PartialOrd for DateTime {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        (self.seconds(), self.time.nanos).partial_cmp(&(other.seconds(), other.time.nanos))
+    }
+}
+
+impl std::fmt::Display for DateTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}T{}", self.date, self.time)
+    }
+}
+
+impl Numeric for DateTime {
+    fn zero() -> Self {
+        DateTime::from_date(Date::zero())
+    }
+    fn name() -> &'static str {
+        "datetime"
+    }
+    fn is_multiple_of(&self, _: &Self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,242 @@
+use std::cmp::Ordering;
+use super::helpers::Numeric;
+
+mod utils {
+    // [-]digits[.digits][(e|E)[+-]digits]
+    pub fn split_number(data: &str) -> Option<(bool, &str, &str, i64)> {
+        let (negative, rest) = match data.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, data.strip_prefix('+').unwrap_or(data)),
+        };
+        let (mantissa, exp) = match rest.find(['e', 'E']) {
+            Some(pos) => (&rest[..pos], rest[pos + 1..].parse::<i64>().ok()?),
+            None => (rest, 0),
+        };
+        let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let is_digits = |v: &str| v.bytes().all(|c| c.is_ascii_digit());
+        if int.is_empty() || !is_digits(int) || !is_digits(frac) {
+            return None;
+        }
+        Some((negative, int, frac, exp))
+    }
+
+    // json numbers don't have leading zeros, a `+` or an empty fraction
+    pub fn is_json_number(data: &str) -> bool {
+        let rest = data.strip_prefix('-').unwrap_or(data);
+        match split_number(rest) {
+            Some((_, int, frac, _)) => {
+                let leading_zero = int.len() > 1 && int.starts_with('0');
+                let empty_fraction = rest.contains('.') && frac.is_empty();
+                !(rest.starts_with('+') || leading_zero || empty_fraction)
+            },
+            None => false,
+        }
+    }
+}
+
+// a floating value with the text it was written with, so 0.30 is not printed as 0.3
+#[derive(Clone)]
+pub struct Float {
+    value: f64,
+    literal: Option<String>,
+}
+
+impl Float {
+    pub fn new(value: f64) -> Float {
+        Float { value, literal: None }
+    }
+
+    // the value is correctly rounded, `_` separators are skipped
+    pub fn parse(literal: &str) -> Result<Float, String> {
+        match literal.replace('_', "").parse::<f64>() {
+            Ok(value) => Float::with_literal(value, literal),
+            Err(_) => Err(format!("Invalid floating value {}", literal)),
+        }
+    }
+
+    pub fn with_literal(value: f64, literal: &str) -> Result<Float, String> {
+        if value.is_finite() {
+            Ok(Float { value, literal: Some(literal.to_string()) })
+        } else {
+            Err(format!("Value {} is out of range for floating", literal))
+        }
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn literal(&self) -> Option<&str> {
+        self.literal.as_deref()
+    }
+
+    pub fn negate(self) -> Float {
+        Float {
+            value: -self.value,
+            literal: self.literal.map(|v| match v.strip_prefix('-') {
+                Some(rest) => rest.to_string(),
+                None => format!("-{}", v),
+            }),
+        }
+    }
+
+    // the literal when json can read it as is. Json has no NaN and infinities
+    pub fn json_string(&self) -> String {
+        match &self.literal {
+            _ if !self.value.is_finite() => String::from("null"),
+            Some(lit) if utils::is_json_number(lit) => lit.clone(),
+            _ => self.value.to_string(),
+        }
+    }
+}
+
+impl From<f64> for Float {
+    fn from(val: f64) -> Self {
+        Float::new(val)
+    }
+}
+
+impl PartialEq for Float {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl PartialOrd for Float {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl std::fmt::Display for Float {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.literal {
+            Some(lit) => write!(f, "{}", lit),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+impl Numeric for Float {
+    fn zero() -> Self {
+        Float::new(0.0)
+    }
+    fn name() -> &'static str {
+        "floating"
+    }
+    fn is_multiple_of(&self, step: &Self) -> bool {
+        if step.value == 0.0 {
+            return true;
+        }
+        // values like 0.3 / 0.1 are never exact, so compare with a small tolerance
+        let ratio = self.value / step.value;
+        (ratio - ratio.round()).abs() <= 1e-9 * ratio.abs().max(1.0)
+    }
+}
+
+// an exact decimal number for money-like values: digits * 10^-scale
+#[derive(Clone)]
+pub struct Decimal {
+    negative: bool,
+    digits: String, // without leading zeros, "0" for zero
+    scale: u32,
+}
+
+impl Decimal {
+    // exponents are limited, so the digits stay reasonable
+    const MAX_EXPONENT: i64 = 1000;
+
+    pub fn parse(data: &str) -> Result<Decimal, String> {
+        let invalid = || format!("Invalid decimal '{}'", data);
+        let (negative, int, frac, exp) = utils::split_number(data).ok_or_else(invalid)?;
+        if exp.abs() > Decimal::MAX_EXPONENT {
+            return Err(format!("Exponent of decimal '{}' is too big", data));
+        }
+        let mut digits = format!("{}{}", int, frac);
+        let mut scale = frac.len() as i64 - exp;
+        if scale < 0 {
+            digits.push_str(&"0".repeat(-scale as usize));
+            scale = 0;
+        }
+        let digits = digits.trim_start_matches('0');
+        let digits = if digits.is_empty() { "0" } else { digits };
+        Ok(Decimal {
+            negative: negative && digits != "0",
+            digits: digits.to_string(),
+            scale: scale as u32,
+        })
+    }
+
+    pub fn negate(mut self) -> Decimal {
+        self.negative = !self.negative && self.digits != "0";
+        self
+    }
+
+    // digits of the value multiplied by 10^scale
+    fn scaled(&self, scale: u32) -> String {
+        let mut res = self.digits.clone();
+        res.push_str(&"0".repeat((scale - self.scale) as usize));
+        res.trim_start_matches('0').to_string()
+    }
+
+    fn compare_abs(&self, other: &Decimal) -> Ordering {
+        let scale = self.scale.max(other.scale);
+        let (a, b) = (self.scaled(scale), other.scaled(scale));
+        a.len().cmp(&b.len()).then_with(|| a.cmp(&b))
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.negative == other.negative && self.compare_abs(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => self.compare_abs(other),
+            (true, true) => other.compare_abs(self),
+        })
+    }
+}
+
+// the scale is kept: 10.50 is written as 10.50
+impl std::fmt::Display for Decimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let scale = self.scale as usize;
+        let digits = if self.digits.len() <= scale {
+            format!("{}{}", "0".repeat(scale + 1 - self.digits.len()), self.digits)
+        } else {
+            self.digits.clone()
+        };
+        let (int, frac) = digits.split_at(digits.len() - scale);
+        write!(f, "{}{}", if self.negative { "-" } else { "" }, int)?;
+        if scale > 0 {
+            write!(f, ".{}", frac)?;
+        }
+        Ok(())
+    }
+}
+
+impl Numeric for Decimal {
+    fn zero() -> Self {
+        Decimal { negative: false, digits: String::from("0"), scale: 0 }
+    }
+    fn name() -> &'static str {
+        "decimal"
+    }
+    // exact for steps up to 37 digits, bigger steps are not checked
+    fn is_multiple_of(&self, step: &Self) -> bool {
+        let scale = self.scale.max(step.scale);
+        let divider = match step.scaled(scale).parse::<u128>() {
+            Ok(0) | Err(_) => return true,
+            Ok(val) if val >= u128::MAX / 10 => return true,
+            Ok(val) => val,
+        };
+        let rest = self.scaled(scale).bytes().fold(0_u128, |acc, c| (acc * 10 + (c - b'0') as u128) % divider);
+        rest == 0
+    }
+}
@@ -0,0 +1,101 @@
+// text encodings of the binary data for the `bytes` type
+#[derive(Clone, Copy, PartialEq)]
+pub enum Encoding {
+    Base64,
+    Base64Url,
+    Hex,
+}
+
+mod utils {
+    pub fn base64_value(c: u8, url: bool) -> Option<u32> {
+        match c {
+            b'A'..=b'Z' => Some((c - b'A') as u32),
+            b'a'..=b'z' => Some((c - b'a') as u32 + 26),
+            b'0'..=b'9' => Some((c - b'0') as u32 + 52),
+            b'+' if !url => Some(62),
+            b'/' if !url => Some(63),
+            b'-' if url => Some(62),
+            b'_' if url => Some(63),
+            _ => None,
+        }
+    }
+
+    // spaces and new lines are allowed, certificates are often split into lines.
+    // Only the canonical form is accepted: base64 is padded, base64url can leave the padding out,
+    // and the unused bits of the last character are zero
+    pub fn decode_base64(data: &str, url: bool) -> Option<Vec<u8>> {
+        let text = data.bytes().filter(|c| !c.is_ascii_whitespace()).collect::<Vec<u8>>();
+        let body = match text.iter().position(|c| *c == b'=') {
+            Some(pos) => {
+                // padding is only at the end and makes the length a multiple of 4
+                if text[pos..].iter().any(|c| *c != b'=') || !text.len().is_multiple_of(4) || text.len() - pos > 2 {
+                    return None;
+                }
+                &text[..pos]
+            },
+            None if !url && !text.len().is_multiple_of(4) => return None,
+            None => &text[..],
+        };
+        if body.len() % 4 == 1 {
+            return None;
+        }
+        let mut result = Vec::new();
+        for chunk in body.chunks(4) {
+            let mut acc: u32 = 0;
+            for c in chunk {
+                acc = (acc << 6) | base64_value(*c, url)?;
+            }
+            let unused = [0, 0, 4, 2, 0][chunk.len()];
+            if acc & ((1 << unused) - 1) != 0 {
+                return None;
+            }
+            acc <<= 6 * (4 - chunk.len()) as u32;
+            let bytes = acc.to_be_bytes();
+            result.extend_from_slice(&bytes[1..chunk.len()]);
+        }
+        Some(result)
+    }
+
+    pub fn decode_hex(data: &str) -> Option<Vec<u8>> {
+        if !data.len().is_multiple_of(2) || !data.bytes().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        (0..data.len()).step_by(2).map(|i| u8::from_str_radix(&data[i..i + 2], 16).ok()).collect()
+    }
+}
+
+impl Encoding {
+    pub fn from_name(name: &str) -> Option<Encoding> {
+        match name {
+            "base64" => Some(Encoding::Base64),
+            "base64url" => Some(Encoding::Base64Url),
+            "hex" => Some(Encoding::Hex),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Base64 => "base64",
+            Encoding::Base64Url => "base64url",
+            Encoding::Hex => "hex",
+        }
+    }
+
+    // names from RFC 4648, the same ones json schema uses
+    pub fn content_encoding(&self) -> &'static str {
+        match self {
+            Encoding::Base64 => "base64",
+            Encoding::Base64Url => "base64url",
+            Encoding::Hex => "base16",
+        }
+    }
+
+    pub fn decode(&self, data: &str) -> Option<Vec<u8>> {
+        match self {
+            Encoding::Base64 => utils::decode_base64(data, false),
+            Encoding::Base64Url => utils::decode_base64(data, true),
+            Encoding::Hex => utils::decode_hex(data),
+        }
+    }
+}
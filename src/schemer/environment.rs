@@ -17,7 +17,7 @@ impl Environment {
         self.aliases.insert(String::from(k), val);
     }
     pub fn has_alias(&self, k: &str) -> bool {
-        self.aliases.get(k).is_some()
+        self.aliases.contains_key(k)
     }
     pub fn get_alias(&self, k: &str) -> Option<&Element> {
         self.aliases.get(k)
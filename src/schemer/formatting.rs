@@ -3,10 +3,149 @@
 use super::objects::*;
 use super::object_base::*;
 use super::helpers::*;
+use super::datetime::{Date, Time, DateTime};
+use super::semver::Version;
+use super::bigint::BigInt;
+use super::decimal::{Float, Decimal};
+
+// how values of the fields marked as `secret` are shown
+#[derive(Clone, Copy, PartialEq)]
+pub enum SecretMode {
+    Redact,
+    Omit,
+    Show,
+}
+
+impl SecretMode {
+    pub fn from_name(name: &str) -> Option<SecretMode> {
+        match name {
+            "redact" => Some(SecretMode::Redact),
+            "omit" => Some(SecretMode::Omit),
+            "show" => Some(SecretMode::Show),
+            _ => None,
+        }
+    }
+}
+
+// how durations and sizes are written to json: "1h30m" or 5400000
+#[derive(Clone, Copy, PartialEq)]
+pub enum UnitFormat {
+    String,
+    Integer,
+}
+
+impl UnitFormat {
+    pub fn from_name(name: &str) -> Option<UnitFormat> {
+        match name {
+            "string" => Some(UnitFormat::String),
+            "integer" => Some(UnitFormat::Integer),
+            _ => None,
+        }
+    }
+}
+
+// the json schema version the schema is written for
+#[derive(Clone, Copy, PartialEq)]
+pub enum SchemaDraft {
+    Draft04,
+    Draft07,
+    Draft201909,
+    Draft202012,
+}
+
+impl SchemaDraft {
+    pub fn from_name(name: &str) -> Option<SchemaDraft> {
+        match name {
+            "draft-04" => Some(SchemaDraft::Draft04),
+            "draft-07" => Some(SchemaDraft::Draft07),
+            "2019-09" => Some(SchemaDraft::Draft201909),
+            "2020-12" => Some(SchemaDraft::Draft202012),
+            _ => None,
+        }
+    }
+
+    pub fn uri(&self) -> &'static str {
+        match self {
+            SchemaDraft::Draft04 => "http://json-schema.org/draft-04/schema#",
+            SchemaDraft::Draft07 => "http://json-schema.org/draft-07/schema#",
+            SchemaDraft::Draft201909 => "https://json-schema.org/draft/2019-09/schema",
+            SchemaDraft::Draft202012 => "https://json-schema.org/draft/2020-12/schema",
+        }
+    }
+
+    // draft-04 has `id`, the later drafts have `$id`
+    pub fn id_keyword(&self) -> &'static str {
+        match self {
+            SchemaDraft::Draft04 => "id",
+            _ => "$id",
+        }
+    }
+
+    pub fn definitions_keyword(&self) -> &'static str {
+        match self {
+            SchemaDraft::Draft04 | SchemaDraft::Draft07 => "definitions",
+            _ => "$defs",
+        }
+    }
+
+    // draft-04 marks `minimum` and `maximum` as exclusive with a boolean
+    pub fn has_boolean_exclusive(&self) -> bool {
+        *self == SchemaDraft::Draft04
+    }
+
+    // draft-04 and draft-07 ignore the keywords next to `$ref`
+    pub fn has_ref_siblings(&self) -> bool {
+        !matches!(self, SchemaDraft::Draft04 | SchemaDraft::Draft07)
+    }
+
+    // `const` appeared in draft-06
+    pub fn has_const(&self) -> bool {
+        *self != SchemaDraft::Draft04
+    }
+}
+
+pub const REDACTED_VALUE: &str = "\"********\"";
+
+mod escape {
+    // quotes, backslashes and control characters, the rest of unicode is written as is
+    pub fn quote(val: &str, schemer: bool) -> String {
+        let mut res = String::with_capacity(val.len() + 2);
+        res.push('"');
+        for c in val.chars() {
+            match c {
+                '"' => res.push_str("\\\""),
+                '\\' => res.push_str("\\\\"),
+                '\n' => res.push_str("\\n"),
+                '\r' => res.push_str("\\r"),
+                '\t' => res.push_str("\\t"),
+                '\u{8}' => res.push_str("\\b"),
+                '\u{c}' => res.push_str("\\f"),
+                '\0' if schemer => res.push_str("\\0"),
+                c if (c as u32) < 0x20 || c == '\u{7f}' => res.push_str(&format!("\\u{:04x}", c as u32)),
+                c => res.push(c),
+            }
+        }
+        res.push('"');
+        res
+    }
+}
+
+pub fn json_quote(val: &str) -> String {
+    escape::quote(val, false)
+}
+
+// schemer reads the same escapes as json and `\0`
+pub fn schemer_quote(val: &str) -> String {
+    escape::quote(val, true)
+}
 
 pub struct Formatting {
     new_line: &'static str,
     shift: String,
+    secrets: SecretMode,
+    units: UnitFormat,
+    sort_keys: bool,
+    draft: SchemaDraft,
 }
 
 impl Formatting {
@@ -14,9 +153,58 @@ impl Formatting {
         Formatting {
             new_line: if shift == 0 { "" } else { "\n" },
             shift: if shift == 0 { String::new() } else { " ".repeat(shift) },
+            secrets: SecretMode::Redact,
+            units: UnitFormat::String,
+            sort_keys: false,
+            draft: SchemaDraft::Draft07,
         }
     }
 
+    pub fn with_units(mut self, units: UnitFormat) -> Formatting {
+        self.units = units;
+        self
+    }
+
+    pub fn units(&self) -> UnitFormat {
+        self.units
+    }
+
+    pub fn with_secrets(mut self, mode: SecretMode) -> Formatting {
+        self.secrets = mode;
+        self
+    }
+
+    pub fn secrets(&self) -> SecretMode {
+        self.secrets
+    }
+
+    pub fn with_draft(mut self, draft: SchemaDraft) -> Formatting {
+        self.draft = draft;
+        self
+    }
+
+    pub fn draft(&self) -> SchemaDraft {
+        self.draft
+    }
+
+    // keys are written in the declaration order unless they are sorted
+    pub fn with_sorted_keys(mut self, sort: bool) -> Formatting {
+        self.sort_keys = sort;
+        self
+    }
+
+    pub fn ordered<'a, V: Clone>(&self, map: &'a OrderedMap<V>) -> Vec<(&'a String, &'a V)> {
+        map.ordered(self.sort_keys)
+    }
+
+    pub fn ordered_fields<'a>(&self, fields: &'a [FieldType]) -> Vec<&'a FieldType> {
+        let mut res = fields.iter().collect::<Vec<&FieldType>>();
+        if self.sort_keys {
+            res.sort_by(|a, b| a.name().cmp(b.name()));
+        }
+        res
+    }
+
     fn sh(&self, shift: usize) -> String {
         self.shift.repeat(shift)
     }
@@ -29,8 +217,8 @@ impl Formatting {
         format!(",{}{}", self.nl(), self.sh(shift))
     }
 
-    pub fn format_array(&self, arr: &Vec<String>, shift: usize) -> String {
-        if arr.len() > 0 {
+    pub fn format_array(&self, arr: &[String], shift: usize) -> String {
+        if !arr.is_empty() {
             format!("{}{}{}{}{}", self.new_line, self.sh(shift + 1), 
             arr.join(&self.nl_sh(shift + 1)), 
             self.new_line, self.sh(shift))
@@ -39,12 +227,13 @@ impl Formatting {
         }
     }
 
-    pub fn format_t_array<T: format::ValueToString>(&self, arr: &Vec<T>, shift: usize) -> String {
-        self.format_array(&arr.iter().map(|v| v.convert()).collect::<Vec<String>>(), shift)
+    // values in schemer syntax
+    pub fn format_t_array<T: format::ValueToString>(&self, arr: &[T], shift: usize) -> String {
+        self.format_array(&arr.iter().map(|v| v.convert_schemer()).collect::<Vec<String>>(), shift)
     }
 
     pub fn format_value<T: format::ValueToString>(&self, val: &T) -> String {
-        val.convert()
+        val.convert_schemer()
     }
 }
 
@@ -52,14 +241,34 @@ pub mod format {
 
     use super::{
         Formatting,
+        json_quote,
+        schemer_quote,
         PossibleArray,
         ObjectType,
         AnyType, 
-        Element
+        Element,
+        FieldType,
+        SecretMode,
+        UnitFormat,
+        REDACTED_VALUE,
+        Duration,
+        ByteSize,
+        UnitValue,
+        Date,
+        Time,
+        DateTime,
+        Version,
+        BigInt,
+        Float,
+        Decimal,
     };
 
+    // `convert` writes json, `convert_schemer` the schemer syntax
     pub trait ValueToString {
         fn convert(&self) -> String;
+        fn convert_schemer(&self) -> String {
+            self.convert()
+        }
     }
 
     impl ValueToString for bool {
@@ -74,7 +283,30 @@ pub mod format {
         }
     }
 
-    impl ValueToString for f64 {
+    impl ValueToString for u64 {
+        fn convert(&self) -> String {
+            self.to_string()
+        }
+    }
+
+    // the digits are written as they are, so ids and hashes are exact in json
+    impl ValueToString for BigInt {
+        fn convert(&self) -> String {
+            self.to_string()
+        }
+    }
+
+    // the number as it was written, when it is a valid json number
+    impl ValueToString for Float {
+        fn convert(&self) -> String {
+            self.json_string()
+        }
+        fn convert_schemer(&self) -> String {
+            if self.value().is_finite() { self.to_string() } else { String::from("null") }
+        }
+    }
+
+    impl ValueToString for Decimal {
         fn convert(&self) -> String {
             self.to_string()
         }
@@ -82,33 +314,88 @@ pub mod format {
 
     impl ValueToString for String {
         fn convert(&self) -> String {
-            format!("\"{}\"", self)
+            json_quote(self)
+        }
+        fn convert_schemer(&self) -> String {
+            schemer_quote(self)
         }
     }
 
-    fn value_format<T: Clone + ValueToString>(value: &PossibleArray<T>, format: &Formatting, shift: usize) -> String {
+    impl ValueToString for Duration {
+        fn convert(&self) -> String {
+            self.to_string()
+        }
+    }
+
+    impl ValueToString for ByteSize {
+        fn convert(&self) -> String {
+            self.to_string()
+        }
+    }
+
+    impl ValueToString for Date {
+        fn convert(&self) -> String {
+            self.to_string()
+        }
+    }
+
+    impl ValueToString for Time {
+        fn convert(&self) -> String {
+            self.to_string()
+        }
+    }
+
+    impl ValueToString for DateTime {
+        fn convert(&self) -> String {
+            self.to_string()
+        }
+    }
+
+    impl ValueToString for Version {
+        fn convert(&self) -> String {
+            self.to_string()
+        }
+    }
+
+    fn value_format_with<T, F>(value: &PossibleArray<T>, format: &Formatting, shift: usize, call: F) -> String 
+        where T: Clone, F: Fn(&T) -> String {
         match value {
-            PossibleArray::Value(val) => { (val as &dyn ValueToString).convert() },
+            PossibleArray::Value(val) => { call(val) },
             PossibleArray::Array(arr) => {
                 format!("[{}]",
-                    format.format_array(&arr.iter().map(|v| {
-                        (v as &dyn ValueToString).convert()
-                    }).collect::<Vec<String>>(), shift + 1)
+                    format.format_array(&arr.iter().map(&call).collect::<Vec<String>>(), shift + 1)
                 )
             },
         }
     }
 
+    fn value_format<T: Clone + ValueToString>(value: &PossibleArray<T>, format: &Formatting, shift: usize) -> String {
+        value_format_with(value, format, shift, |v| v.convert())
+    }
+
+    fn unit_value_format<T: Clone + ValueToString + UnitValue>(value: &PossibleArray<T>, format: &Formatting, shift: usize) -> String {
+        match format.units() {
+            UnitFormat::String => value_format_with(value, format, shift, |v| json_quote(&v.convert())),
+            UnitFormat::Integer => value_format_with(value, format, shift, |v| v.units().to_string()),
+        }
+    }
+
+    // dates, times and versions are strings in json
+    fn quoted_value_format<T: Clone + ValueToString>(value: &PossibleArray<T>, format: &Formatting, shift: usize) -> String {
+        value_format_with(value, format, shift, |v| json_quote(&v.convert()))
+    }
+
     fn object_format(obj: &ObjectType, format: &Formatting, shift: usize) -> String {
         match obj.value() {
             PossibleArray::Value(val) => {
-                let str_value = match &**val {
+                let fields = match &**val {
                     Some(unboxed) => {
                         unboxed.fields()
                     },
                     None => obj.fields(),
-                }.iter().map(|(k, v)| {
-                    format!("\"{}\": {}", k, element_format_impl(v.value(), format, shift + 1))
+                };
+                let str_value = format.ordered(fields).into_iter().filter_map(|(_, v)| {
+                    field_format(v, format, shift + 1)
                 }).collect::<Vec<String>>();
                 format!("{{{}}}", format.format_array(&str_value, shift))
             },
@@ -149,12 +436,52 @@ pub mod format {
         }
     }
 
+    // the value of the field, values of `secret` fields follow `--secrets`
+    pub fn field_value_format(field: &FieldType, format: &Formatting, shift: usize) -> Option<String> {
+        // addresses without a value are left out
+        if let Element::Net(v) = field.value() {
+            if matches!(v.value(), PossibleArray::Value(val) if val.is_empty()) {
+                return None;
+            }
+        }
+        if field.options().has_bool("secret") {
+            match format.secrets() {
+                SecretMode::Omit => None,
+                SecretMode::Redact => Some(REDACTED_VALUE.to_string()),
+                SecretMode::Show => Some(element_format_impl(field.value(), format, shift)),
+            }
+        } else {
+            Some(element_format_impl(field.value(), format, shift))
+        }
+    }
+
+    pub fn field_format(field: &FieldType, format: &Formatting, shift: usize) -> Option<String> {
+        let value = field_value_format(field, format, shift)?;
+        Some(format!("{}: {}", json_quote(field.name()), value))
+    }
+
     pub fn element_format_impl(element: &Element, format: &Formatting, shift: usize) -> String {
         match element {
             Element::Boolean(v) => { value_format(v.value(), format, shift) },
             Element::String(v) => { value_format(v.value(), format, shift) },
             Element::Integer(v) => { value_format(v.value(), format, shift) },
+            Element::Unsigned(v) => { value_format(v.value(), format, shift) },
+            Element::BigInt(v) => { value_format(v.value(), format, shift) },
             Element::Floating(v) => { value_format(v.value(), format, shift) },
+            Element::Decimal(v) => { value_format(v.value(), format, shift) },
+            Element::Duration(v) => { unit_value_format(v.value(), format, shift) },
+            Element::Size(v) => { unit_value_format(v.value(), format, shift) },
+            Element::Date(v) => { quoted_value_format(v.value(), format, shift) },
+            Element::Time(v) => { quoted_value_format(v.value(), format, shift) },
+            Element::DateTime(v) => { quoted_value_format(v.value(), format, shift) },
+            // an address without a value isn't an empty string
+            Element::Net(v) => match v.value() {
+                PossibleArray::Value(val) if val.is_empty() => "null".to_string(),
+                _ => value_format(v.value(), format, shift),
+            },
+            Element::Semver(v) => { quoted_value_format(v.value(), format, shift) },
+            Element::Bytes(v) => { value_format(v.value(), format, shift) },
+            Element::Path(v) => { value_format(v.value(), format, shift) },
             Element::Object(v) => { object_format(v, format, shift) },
             Element::Any(v) => { any_format(v, format, shift) },
             Element::None => "".to_string(),
@@ -162,23 +489,20 @@ pub mod format {
     }
 }
 
-pub fn element_format(element: &Element, shift: usize) -> String {
-    let format = Formatting::new(shift);
-    format::element_format_impl(element, &format, 0)
+pub fn element_format(element: &Element, format: &Formatting) -> String {
+    format::element_format_impl(element, format, 0)
 }
 
-pub fn array_format(module: &Vec<FieldType>, shift: usize) -> String {
-    let format = Formatting::new(shift);
-    let elements = module.iter().map(|v| {
-        format!("\"{}\": {}", v.name(), format::element_format_impl(v.value(), &format, 1))
+pub fn array_format(module: &[FieldType], format: &Formatting) -> String {
+    let elements = format.ordered_fields(module).into_iter().filter_map(|v| {
+        format::field_format(v, format, 1)
     }).collect::<Vec<String>>();
     format!("{{{}}}", format.format_array(&elements, 0))
 }
 
-pub fn ref_array_format(module: &Vec<&FieldType>, shift: usize) -> String {
-    let format = Formatting::new(shift);
-    let elements = module.iter().map(|v| {
-        format!("\"{}\": {}", v.name(), format::element_format_impl(v.value(), &format, 1))
+pub fn ref_array_format(module: &Vec<&FieldType>, format: &Formatting) -> String {
+    let elements = module.iter().filter_map(|v| {
+        format::field_format(v, format, 1)
     }).collect::<Vec<String>>();
     format!("{{{}}}", format.format_array(&elements, 0))
 }
@@ -0,0 +1,568 @@
+use std::collections::HashMap;
+use super::objects::*;
+use super::object_base::ObjectBase;
+use super::helpers::*;
+use super::json::{JsonValue, pointer_token, pointer_unescape};
+use super::decimal::Float;
+use super::datetime::{Date, Time, DateTime, TIME_PATTERN};
+use super::network::NetKind;
+use super::validate::validate_field;
+use super::overrides::element_with_value;
+
+// keywords that only describe the schema and the definitions that are read as aliases
+const ANNOTATIONS: [&str; 8] = ["$schema", "$id", "id", "title", "description", "$comment", "$defs", "definitions"];
+
+// keywords that become field options
+const OPTION_KEYWORDS: [&str; 6] = ["readOnly", "readonly", "writeOnly", "x-secret", "deprecated", "examples"];
+
+mod utils {
+    use super::*;
+
+    // 1000.0 and 1e3 are integers too
+    pub fn integer(val: &JsonValue) -> Option<i64> {
+        if let Some(v) = val.as_i64() {
+            return Some(v);
+        }
+        let v = val.as_number()?.parse::<f64>().ok()?;
+        // larger values are not exact in f64
+        if v.fract() == 0.0 && v.abs() <= 9_007_199_254_740_992.0 {
+            Some(v as i64)
+        } else {
+            None
+        }
+    }
+
+    // the value fits the bounds, the enum and the step
+    pub fn check_number<T: Numeric>(num: &NumberType<T>, val: &T) -> bool {
+        num.check_minmax(val.clone()) && num.check_enum(val.clone()) && num.check_step(val.clone())
+    }
+    pub fn floating(val: &JsonValue) -> Option<Float> {
+        Float::parse(val.as_number()?).ok()
+    }
+    pub fn date(val: &JsonValue) -> Option<Date> {
+        Date::parse(val.as_str()?).ok()
+    }
+    pub fn time(val: &JsonValue) -> Option<Time> {
+        Time::parse(val.as_str()?).ok()
+    }
+    pub fn date_time(val: &JsonValue) -> Option<DateTime> {
+        DateTime::parse(val.as_str()?).ok()
+    }
+
+    pub fn flag() -> Element {
+        Element::Boolean(BooleanType::from(true))
+    }
+
+    // `urn:schemer:a.b` is the id json_schema writes for `mod a.b`
+    pub fn module_name(id: &str) -> Option<&str> {
+        let name = id.strip_prefix("urn:schemer:")?;
+        let valid = name.split('.').all(|part| {
+            !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        });
+        if valid { Some(name) } else { None }
+    }
+
+    // values of the enum with their labels: `enum`, `const` or `oneOf` with `const` and `title`
+    pub fn enum_values(schema: &OrderedMap<JsonValue>) -> Option<Vec<(&JsonValue, Option<&str>)>> {
+        if let Some(values) = schema.get("enum").and_then(|v| v.as_array()) {
+            return Some(values.iter().map(|v| (v, None)).collect());
+        }
+        if let Some(value) = schema.get("const") {
+            return Some(vec![(value, None)]);
+        }
+        let items = schema.get("oneOf")?.as_array()?;
+        items.iter().map(|item| {
+            let value = match (item.get("const"), item.get("enum").and_then(|v| v.as_array())) {
+                (Some(value), _) => value,
+                (None, Some(values)) if values.len() == 1 => &values[0],
+                _ => return None,
+            };
+            Some((value, item.get("title").and_then(|v| v.as_str())))
+        }).collect()
+    }
+}
+
+struct Importer<'a> {
+    root: &'a JsonValue,
+    warnings: Vec<String>,
+    // references that are imported or being imported, `None` while in progress
+    refs: HashMap<String, Option<(Element, Options)>>,
+}
+
+impl<'a> Importer<'a> {
+    fn warn(&mut self, path: &str, msg: &str) {
+        let at = if path.is_empty() { "/" } else { path };
+        self.warnings.push(format!("{}: {}", at, msg));
+    }
+
+    fn warn_unknown(&mut self, schema: &OrderedMap<JsonValue>, path: &str, known: &[&str]) {
+        for (key, _) in schema.iter() {
+            if !known.contains(&key.as_str()) && !ANNOTATIONS.contains(&key.as_str()) && !OPTION_KEYWORDS.contains(&key.as_str()) {
+                self.warn(path, &format!("'{}' is not supported", key));
+            }
+        }
+    }
+
+    fn read_options(&mut self, schema: &OrderedMap<JsonValue>, opts: &mut Options) {
+        if schema.get("readOnly").or_else(|| schema.get("readonly")).and_then(|v| v.as_bool()) == Some(true) {
+            opts.add("readonly", utils::flag());
+        }
+        if schema.get("writeOnly").or_else(|| schema.get("x-secret")).and_then(|v| v.as_bool()) == Some(true) {
+            opts.add("secret", utils::flag());
+        }
+        if schema.get("deprecated").and_then(|v| v.as_bool()) == Some(true) {
+            opts.add("deprecated", utils::flag());
+        }
+    }
+
+    // "#/$defs/name" or "#/definitions/name"
+    fn import_ref(&mut self, reference: &str, path: &str) -> Element {
+        if let Some(done) = self.refs.get(reference) {
+            return match done {
+                Some((element, _)) => element.clone(),
+                None => {
+                    self.warn(path, &format!("recursive reference '{}' is written as 'any'", reference));
+                    Element::Any(AnyType::new())
+                },
+            };
+        }
+        let target = reference.strip_prefix('#').and_then(|pointer| {
+            pointer.split('/').skip(1).try_fold(self.root, |node, token| {
+                let token = pointer_unescape(token);
+                match node {
+                    JsonValue::Object(map) => map.get(&token),
+                    JsonValue::Array(arr) => arr.get(token.parse::<usize>().ok()?),
+                    _ => None,
+                }
+            })
+        });
+        match target {
+            Some(schema) => {
+                self.refs.insert(reference.to_string(), None);
+                let pointer = reference[1..].to_string();
+                let (element, opts) = self.import_schema(schema, &pointer);
+                self.refs.insert(reference.to_string(), Some((element.clone(), opts)));
+                element
+            },
+            None => {
+                self.warn(path, &format!("reference '{}' is not found", reference));
+                Element::Any(AnyType::new())
+            },
+        }
+    }
+
+    // the type from `type` or from the keywords the schema has
+    fn schema_type(&mut self, schema: &OrderedMap<JsonValue>, path: &str) -> Option<String> {
+        match schema.get("type") {
+            Some(JsonValue::String(name)) => Some(name.clone()),
+            Some(JsonValue::Array(types)) => {
+                if types.iter().any(|v| v.as_str() == Some("null")) {
+                    self.warn(path, "'null' values are not supported");
+                }
+                let names = types.iter().filter_map(|v| v.as_str()).filter(|v| *v != "null").collect::<Vec<&str>>();
+                if names.len() == 1 {
+                    Some(names[0].to_string())
+                } else {
+                    self.warn(path, "a union of types is written as 'any'");
+                    None
+                }
+            },
+            Some(_) => {
+                self.warn(path, "'type' should be a string or an array");
+                None
+            },
+            None if schema.contains_key("properties") => Some(String::from("object")),
+            None if schema.contains_key("items") => Some(String::from("array")),
+            None => match utils::enum_values(schema).as_ref().and_then(|v| v.first()) {
+                Some((JsonValue::String(_), _)) => Some(String::from("string")),
+                Some((JsonValue::Boolean(_), _)) => Some(String::from("boolean")),
+                Some((JsonValue::Number(lit), _)) => {
+                    Some(String::from(if lit.parse::<i64>().is_ok() { "integer" } else { "number" }))
+                },
+                _ => None,
+            },
+        }
+    }
+
+    fn import_schema(&mut self, schema: &JsonValue, path: &str) -> (Element, Options) {
+        let mut opts = Options::new();
+        let schema = match schema {
+            JsonValue::Object(map) => map,
+            JsonValue::Boolean(true) => return (Element::Any(AnyType::new()), opts),
+            _ => {
+                self.warn(path, "the schema should be an object");
+                return (Element::Any(AnyType::new()), opts);
+            },
+        };
+        self.read_options(schema, &mut opts);
+        // draft-04 and draft-07 keep the keywords next to a reference in a one-item `allOf`
+        let reference = schema.get("$ref").and_then(|v| v.as_str()).or_else(|| match schema.get("allOf") {
+            Some(JsonValue::Array(items)) if items.len() == 1 => items[0].get("$ref").and_then(|v| v.as_str()),
+            _ => None,
+        });
+        let element = match reference {
+            Some(reference) => {
+                self.warn_unknown(schema, path, &["$ref", "allOf"]);
+                self.import_ref(reference, path)
+            },
+            None => match self.schema_type(schema, path).as_deref() {
+                Some("array") => self.import_array(schema, path),
+                Some(name) => self.import_value(name, schema, path),
+                None => {
+                    self.warn_unknown(schema, path, &["type"]);
+                    Element::Any(AnyType::new())
+                },
+            },
+        };
+        self.read_examples(schema, &element, &mut opts, path);
+        (element, opts)
+    }
+
+    // examples are kept when they are valid values of the field
+    fn read_examples(&mut self, schema: &OrderedMap<JsonValue>, element: &Element, opts: &mut Options, path: &str) {
+        let items = match schema.get("examples") {
+            Some(JsonValue::Array(items)) => items,
+            Some(_) => {
+                self.warn(path, "'examples' should be an array");
+                return;
+            },
+            None => return,
+        };
+        let fld = FieldType::new(String::new(), element.clone(), Options::new());
+        let mut examples = AnyType::new_array();
+        for (id, item) in items.iter().enumerate() {
+            if validate_field(&fld, item, false).is_empty() {
+                examples.add_value(element_with_value(element, item));
+            } else {
+                self.warn(&format!("{}/examples/{}", path, id), "the example doesn't match the schema, it is skipped");
+            }
+        }
+        if !examples.value().as_array().is_some_and(|v| v.is_empty()) {
+            opts.add("examples", Element::Any(examples));
+        }
+    }
+
+    fn import_array(&mut self, schema: &OrderedMap<JsonValue>, path: &str) -> Element {
+        self.warn_unknown(schema, path, &["type", "items", "default"]);
+        let items_path = format!("{}/items", path);
+        let items = match schema.get("items") {
+            Some(JsonValue::Object(items)) => items,
+            Some(_) => {
+                self.warn(&items_path, "only a single schema of the items is supported");
+                return Element::Any(AnyType::new_array());
+            },
+            None => return Element::Any(AnyType::new_array()),
+        };
+        let element = match self.schema_type(items, &items_path).as_deref() {
+            Some("array") => {
+                self.warn(&items_path, "arrays of arrays are written as 'any' values");
+                return Element::Any(AnyType::new_array());
+            },
+            Some(name) => self.import_value(name, items, &items_path),
+            None => {
+                self.warn_unknown(items, &items_path, &["type"]);
+                Element::Any(AnyType::new())
+            },
+        };
+        let default = match schema.get("default") {
+            Some(JsonValue::Array(values)) => values.clone(),
+            Some(_) => {
+                self.warn(path, "the default should be an array");
+                Vec::new()
+            },
+            None => Vec::new(),
+        };
+        match self.make_array(element.clone(), &default) {
+            Some(element) => element,
+            None => {
+                self.warn(path, "the default doesn't match the items");
+                self.make_array(element, &[]).unwrap_or(Element::Any(AnyType::new_array()))
+            },
+        }
+    }
+
+    // the element as an array with the values
+    fn make_array(&self, element: Element, values: &[JsonValue]) -> Option<Element> {
+        fn fill<T: Numeric>(mut num: NumberType<T>, values: &[JsonValue], convert: fn(&JsonValue) -> Option<T>) -> Option<NumberType<T>> {
+            num.make_array();
+            for v in values {
+                let v = convert(v)?;
+                if !utils::check_number(&num, &v) {
+                    return None;
+                }
+                num.add_value(v);
+            }
+            Some(num)
+        }
+        Some(match element {
+            Element::String(mut v) => {
+                v.make_array();
+                for val in values {
+                    let val = val.as_str()?;
+                    if !v.check_enum(&val.to_string()) {
+                        return None;
+                    }
+                    v.add_value(val);
+                }
+                Element::String(v)
+            },
+            Element::Boolean(mut v) => {
+                v.make_array();
+                for val in values {
+                    v.add_value(val.as_bool()?);
+                }
+                Element::Boolean(v)
+            },
+            Element::Net(mut v) => {
+                v.make_array();
+                for val in values {
+                    let val = v.kind().normalize(val.as_str()?).ok()?;
+                    if !v.check_enum(&val) {
+                        return None;
+                    }
+                    v.add_value(&val);
+                }
+                Element::Net(v)
+            },
+            Element::Integer(v) => Element::Integer(fill(v, values, utils::integer)?),
+            Element::Floating(v) => Element::Floating(fill(v, values, utils::floating)?),
+            Element::Date(v) => Element::Date(fill(v, values, utils::date)?),
+            Element::Time(v) => Element::Time(fill(v, values, utils::time)?),
+            Element::DateTime(v) => Element::DateTime(fill(v, values, utils::date_time)?),
+            Element::Object(mut v) => {
+                if !values.is_empty() {
+                    return None;
+                }
+                v.make_array();
+                Element::Object(v)
+            },
+            _ => {
+                if !values.is_empty() {
+                    return None;
+                }
+                Element::Any(AnyType::new_array())
+            },
+        })
+    }
+
+    fn import_value(&mut self, name: &str, schema: &OrderedMap<JsonValue>, path: &str) -> Element {
+        match name {
+            "object" => Element::Object(self.import_object(schema, path)),
+            "boolean" => {
+                self.warn_unknown(schema, path, &["type", "default"]);
+                let mut val = BooleanType::new();
+                match schema.get("default").map(|v| v.as_bool()) {
+                    Some(Some(v)) => val.add_value(v),
+                    Some(None) => self.warn(path, "the default should be a boolean"),
+                    None => {},
+                }
+                Element::Boolean(val)
+            },
+            "integer" => Element::Integer(self.import_number(schema, path, utils::integer)),
+            "number" => Element::Floating(self.import_number(schema, path, utils::floating)),
+            "string" => match schema.get("format").and_then(|v| v.as_str()) {
+                Some("date") => Element::Date(self.import_number(schema, path, utils::date)),
+                Some("time") => Element::Time(self.import_number(schema, path, utils::time)),
+                Some("date-time") => Element::DateTime(self.import_number(schema, path, utils::date_time)),
+                Some("ipv4") => Element::Net(self.import_net(NetKind::Ipv4, schema, path)),
+                Some("ipv6") => Element::Net(self.import_net(NetKind::Ipv6, schema, path)),
+                // the pattern json_schema writes for times
+                None if schema.get("pattern").and_then(|v| v.as_str()) == Some(TIME_PATTERN) => {
+                    Element::Time(self.import_number(schema, path, utils::time))
+                },
+                _ => Element::String(self.import_string(schema, path)),
+            },
+            _ => {
+                self.warn(path, &format!("type '{}' is written as 'any'", name));
+                Element::Any(AnyType::new())
+            },
+        }
+    }
+
+    fn import_object(&mut self, schema: &OrderedMap<JsonValue>, path: &str) -> ObjectType {
+        self.warn_unknown(schema, path, &["type", "properties", "required", "additionalProperties"]);
+        // schemer objects have only the fields they declare
+        match schema.get("additionalProperties") {
+            None | Some(JsonValue::Boolean(false)) => {},
+            Some(_) => self.warn(path, "additional properties are not supported"),
+        }
+        let required = schema.get("required").and_then(|v| v.as_array()).map(|v| {
+            v.iter().filter_map(|n| n.as_str()).map(String::from).collect::<Vec<String>>()
+        }).unwrap_or_default();
+        let mut obj = ObjectType::new();
+        if let Some(JsonValue::Object(props)) = schema.get("properties") {
+            for (name, prop) in props.iter() {
+                let (element, mut opts) = self.import_schema(prop, &format!("{}/properties/{}", path, pointer_token(name)));
+                if required.contains(name) {
+                    opts.add("required", utils::flag());
+                }
+                obj.add_field(FieldType::new(name.clone(), element, opts));
+            }
+        }
+        for name in &required {
+            if !obj.has_field(name) {
+                self.warn(path, &format!("required field '{}' has no schema", name));
+            }
+        }
+        obj
+    }
+
+    fn import_string(&mut self, schema: &OrderedMap<JsonValue>, path: &str) -> StringType {
+        self.warn_unknown(schema, path, &["type", "enum", "const", "oneOf", "default", "format"]);
+        if let Some(format) = schema.get("format").and_then(|v| v.as_str()) {
+            self.warn(path, &format!("format '{}' is not supported", format));
+        }
+        let mut val = StringType::new();
+        for (value, label) in utils::enum_values(schema).unwrap_or_default() {
+            match value.as_str() {
+                Some(v) => {
+                    val.add_enum_value(v);
+                    if let Some(label) = label {
+                        val.set_enum_label(label);
+                    }
+                },
+                None => self.warn(path, "enum values should be strings"),
+            }
+        }
+        match schema.get("default").map(|v| v.as_str()) {
+            Some(Some(v)) if !val.check_enum(&v.to_string()) => self.warn(path, "the default is not one of the enum values, it is dropped"),
+            Some(Some(v)) => val.add_value(v),
+            Some(None) => self.warn(path, "the default should be a string"),
+            None => {},
+        }
+        val
+    }
+
+    fn import_net(&mut self, kind: NetKind, schema: &OrderedMap<JsonValue>, path: &str) -> NetType {
+        self.warn_unknown(schema, path, &["type", "enum", "const", "oneOf", "default", "format"]);
+        let mut val = NetType::new(kind);
+        for (value, label) in utils::enum_values(schema).unwrap_or_default() {
+            match value.as_str().and_then(|v| kind.normalize(v).ok()) {
+                Some(v) => {
+                    val.add_enum_value(&v);
+                    if let Some(label) = label {
+                        val.set_enum_label(label);
+                    }
+                },
+                None => self.warn(path, &format!("enum values should be {} addresses", kind.name())),
+            }
+        }
+        match schema.get("default").map(|v| v.as_str().and_then(|v| kind.normalize(v).ok())) {
+            Some(Some(v)) if !val.check_enum(&v) => self.warn(path, "the default is not one of the enum values, it is dropped"),
+            Some(Some(v)) => val.add_value(&v),
+            Some(None) => self.warn(path, &format!("the default should be an {} address", kind.name())),
+            None => {},
+        }
+        val
+    }
+
+    // numbers, dates and times: enums, bounds, steps and defaults
+    fn import_number<T: Numeric>(&mut self, schema: &OrderedMap<JsonValue>, path: &str, convert: fn(&JsonValue) -> Option<T>) -> NumberType<T> {
+        let known = ["type", "enum", "const", "oneOf", "default", "format", "pattern",
+            "minimum", "maximum", "exclusiveMinimum", "exclusiveMaximum", "multipleOf"];
+        self.warn_unknown(schema, path, &known);
+        let mut val = NumberType::<T>::new();
+        let mut read = |importer: &mut Importer, key: &str| -> Option<T> {
+            let value = schema.get(key)?;
+            let res = convert(value);
+            if res.is_none() {
+                importer.warn(path, &format!("'{}' is not a valid {}", key, T::name()));
+            }
+            res
+        };
+        for (value, label) in utils::enum_values(schema).unwrap_or_default() {
+            match convert(value) {
+                Some(v) => {
+                    val.add_enum_value(v);
+                    if let Some(label) = label {
+                        val.set_enum_label(label);
+                    }
+                },
+                None => self.warn(path, &format!("enum values should be {} values", T::name())),
+            }
+        }
+        if let Some(v) = read(self, "minimum") {
+            val.set_min(v);
+            if schema.get("exclusiveMinimum").and_then(|v| v.as_bool()) == Some(true) {
+                val.exclude_min();
+            }
+        }
+        if let Some(v) = read(self, "maximum") {
+            val.set_max(v);
+            if schema.get("exclusiveMaximum").and_then(|v| v.as_bool()) == Some(true) {
+                val.exclude_max();
+            }
+        }
+        // draft-06 and later write exclusive bounds as numbers
+        if schema.get("exclusiveMinimum").and_then(|v| v.as_bool()).is_none() {
+            if let Some(v) = read(self, "exclusiveMinimum") {
+                val.set_min(v);
+                val.exclude_min();
+            }
+        }
+        if schema.get("exclusiveMaximum").and_then(|v| v.as_bool()).is_none() {
+            if let Some(v) = read(self, "exclusiveMaximum") {
+                val.set_max(v);
+                val.exclude_max();
+            }
+        }
+        if let Some(v) = read(self, "multipleOf") {
+            val.set_step(v);
+        }
+        if let Some(v) = read(self, "default") {
+            if utils::check_number(&val, &v) {
+                val.add_value(v);
+            } else {
+                self.warn(path, &format!("the default {} doesn't fit the bounds, the enum or the step, it is dropped", v.to_string()));
+            }
+        }
+        val
+    }
+
+    fn import_module(&mut self) -> Module {
+        let mut module = Module::new();
+        let root = match self.root {
+            JsonValue::Object(map) => map,
+            _ => {
+                self.warn("", "the schema should be an object");
+                return module;
+            },
+        };
+        if let Some(name) = root.get("$id").or_else(|| root.get("id")).and_then(|v| v.as_str()).and_then(utils::module_name) {
+            module.set_name(name.to_string());
+        }
+        for keyword in ["$defs", "definitions"] {
+            if let Some(JsonValue::Object(defs)) = root.get(keyword) {
+                for (name, _) in defs.iter() {
+                    let reference = format!("#/{}/{}", keyword, pointer_token(name));
+                    self.import_ref(&reference, "");
+                    if let Some(Some((element, opts))) = self.refs.get(&reference) {
+                        module.add_alias(FieldType::new(name.clone(), element.clone(), opts.clone()));
+                    }
+                }
+            }
+        }
+        // top-level properties are the fields of the module, any other schema is the `main` field
+        if self.schema_type(root, "").as_deref() == Some("object") && !root.contains_key("$ref") {
+            let obj = self.import_object(root, "");
+            for (_, fld) in obj.fields().iter() {
+                module.add_field(fld.clone());
+            }
+        } else {
+            let (element, opts) = self.import_schema(self.root, "");
+            module.add_field(FieldType::new(String::from("main"), element, opts));
+        }
+        module
+    }
+}
+
+// the module with the warnings about the parts of the schema that were skipped
+pub fn from_json_schema(schema: &JsonValue) -> (Module, Vec<String>) {
+    let mut importer = Importer {
+        root: schema,
+        warnings: Vec::new(),
+        refs: HashMap::new(),
+    };
+    let module = importer.import_module();
+    (module, importer.warnings)
+}
@@ -2,22 +2,26 @@
 #[derive(Clone)]
 pub struct Interval<T: Clone> {
     min_max: (Option<T>, Option<T>),
+    exclusive: (bool, bool),
 }
 
-impl<T> Interval<T> where T: std::cmp::PartialOrd + Copy {
+impl<T> Interval<T> where T: std::cmp::PartialOrd + Clone {
     pub fn none() -> Interval<T> {
         Interval {
-            min_max: (None, None)
+            min_max: (None, None),
+            exclusive: (false, false),
         }
     }
     pub fn check(&self, val: T) -> bool {
-        return match &self.min_max.0 {
-            Some(min_val) => *min_val <= val,
+        let above_min = match &self.min_max.0 {
+            Some(min_val) => if self.exclusive.0 { *min_val < val } else { *min_val <= val },
             None => true,
-        } && match &self.min_max.1 {
-            Some(max_val) => val <= *max_val,
+        };
+        let below_max = match &self.min_max.1 {
+            Some(max_val) => if self.exclusive.1 { val < *max_val } else { val <= *max_val },
             None => true,
-        }
+        };
+        above_min && below_max
     }
     pub fn set_min(&mut self, val: T) {
         self.min_max.0 = Some(val);
@@ -25,6 +29,18 @@ impl<T> Interval<T> where T: std::cmp::PartialOrd + Copy {
     pub fn set_max(&mut self, val: T) {
         self.min_max.1 = Some(val);
     }
+    pub fn exclude_min(&mut self) {
+        self.exclusive.0 = true;
+    }
+    pub fn exclude_max(&mut self) {
+        self.exclusive.1 = true;
+    }
+    pub fn is_min_exclusive(&self) -> bool {
+        self.exclusive.0
+    }
+    pub fn is_max_exclusive(&self) -> bool {
+        self.exclusive.1
+    }
 
     pub fn has_min(&self) -> bool {
         self.min_max.0.is_some()
@@ -37,13 +53,13 @@ impl<T> Interval<T> where T: std::cmp::PartialOrd + Copy {
     }
     pub fn min(&self, default: T) -> T {
         match &self.min_max.0 {
-            Some(val) => *val,
+            Some(val) => val.clone(),
             None => default,
         }
     }
     pub fn max(&self, default: T) -> T {
         match &self.min_max.1 {
-            Some(val) => *val,
+            Some(val) => val.clone(),
             None => default,
         }
     }
@@ -51,13 +67,15 @@ impl<T> Interval<T> where T: std::cmp::PartialOrd + Copy {
 
 #[derive(Clone)]
 pub struct Enum<T: Clone> {
-    values: Vec<T>
+    values: Vec<T>,
+    labels: Vec<Option<String>>,
 }
 
 impl<T> Enum<T> where T: std::cmp::PartialEq + Clone {
     pub fn create_with(val: T) -> Enum<T> {
         Enum {
             values: vec!(val),
+            labels: vec!(None),
         }
     }
     pub fn check(&self, val: &T) -> bool {
@@ -68,15 +86,37 @@ impl<T> Enum<T> where T: std::cmp::PartialEq + Clone {
             false
         } else {
             self.values.push(val);
+            self.labels.push(None);
             true
         }
     }
     pub fn values(&self) -> &Vec<T> {
         &self.values
     }
+    pub fn set_last_label(&mut self, label: &str) {
+        if let Some(last) = self.labels.last_mut() {
+            *last = Some(label.to_string());
+        }
+    }
+    pub fn label(&self, id: usize) -> Option<&str> {
+        match self.labels.get(id) {
+            Some(Some(label)) => Some(label),
+            _ => None,
+        }
+    }
+    pub fn has_labels(&self) -> bool {
+        self.labels.iter().any(|l| l.is_some())
+    }
+    pub fn map<U, F>(&self, call: F) -> Enum<U> 
+        where U: PartialEq + Clone, F: Fn(&T) -> U {
+        Enum {
+            values: self.values.iter().map(call).collect(),
+            labels: self.labels.clone(),
+        }
+    }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub enum PossibleArray<T> where T: Clone {
     Value(T),
     Array(Vec<T>),
@@ -101,7 +141,7 @@ impl<T> PossibleArray<T> where T: Clone {
     }
     pub fn as_value(&self) -> Option<&T> {
         match &self {
-            PossibleArray::Value(val) => Some(&val),
+            PossibleArray::Value(val) => Some(val),
             PossibleArray::Array(_) => None,
         }
     }
@@ -113,13 +153,58 @@ impl<T> PossibleArray<T> where T: Clone {
     }
 }
 
-pub trait Numeric: Copy + 
-                Clone +
+// a map that keeps the keys in the order they were added
+#[derive(Clone)]
+pub struct OrderedMap<V: Clone> {
+    entries: Vec<(String, V)>,
+}
+
+impl<V> OrderedMap<V> where V: Clone {
+    pub fn new() -> OrderedMap<V> {
+        OrderedMap { entries: Vec::new() }
+    }
+    fn position(&self, key: &str) -> Option<usize> {
+        self.entries.iter().position(|(k, _)| k == key)
+    }
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.position(key).is_some()
+    }
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.position(key).map(|pos| &self.entries[pos].1)
+    }
+    // a new value for an existing key keeps its place
+    pub fn insert(&mut self, key: String, value: V) {
+        match self.position(&key) {
+            Some(pos) => self.entries[pos].1 = value,
+            None => self.entries.push((key, value)),
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &V)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.entries.iter().map(|(_, v)| v)
+    }
+    // entries in the declaration order or sorted by the key
+    pub fn ordered(&self, sort: bool) -> Vec<(&String, &V)> {
+        let mut res = self.iter().collect::<Vec<(&String, &V)>>();
+        if sort {
+            res.sort_by(|a, b| a.0.cmp(b.0));
+        }
+        res
+    }
+}
+
+pub trait Numeric: Clone +
                 PartialOrd + 
                 PartialEq + 
                 std::string::ToString {
     fn zero() -> Self;
     fn name() -> &'static str;
+    fn is_multiple_of(&self, step: &Self) -> bool;
 }
 
 impl Numeric for i64 {
@@ -129,13 +214,169 @@ impl Numeric for i64 {
     fn name() -> &'static str {
         "integer"
     }
+    fn is_multiple_of(&self, step: &Self) -> bool {
+        *step == 0 || self % step == 0
+    }
 }
 
-impl Numeric for f64 {
+impl Numeric for u64 {
     fn zero() -> Self {
-        0.0 as Self
+        0 as Self
     }
     fn name() -> &'static str {
-        "floating"
+        "unsigned"
+    }
+    fn is_multiple_of(&self, step: &Self) -> bool {
+        *step == 0 || u64::is_multiple_of(*self, *step)
+    }
+}
+
+pub const DURATION_UNITS: [(&str, u64); 5] = [
+    ("d", 86_400_000),
+    ("h", 3_600_000),
+    ("m", 60_000),
+    ("s", 1_000),
+    ("ms", 1),
+];
+
+pub const SIZE_UNITS: [(&str, u64); 9] = [
+    ("TiB", 1 << 40),
+    ("GiB", 1 << 30),
+    ("MiB", 1 << 20),
+    ("KiB", 1 << 10),
+    ("TB", 1_000_000_000_000),
+    ("GB", 1_000_000_000),
+    ("MB", 1_000_000),
+    ("KB", 1_000),
+    ("B", 1),
+];
+
+pub fn unit_multiplier(units: &[(&str, u64)], name: &str) -> Option<u64> {
+    units.iter().find(|(n, _)| *n == name).map(|(_, m)| *m)
+}
+
+// values with units are kept as integers in the smallest unit 
+pub trait UnitValue {
+    fn units(&self) -> u64;
+    fn unit_name() -> &'static str;
+    fn pattern() -> &'static str;
+}
+
+// milliseconds
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+pub struct Duration(u64);
+
+impl Duration {
+    pub fn from_millis(val: u64) -> Duration {
+        Duration(val)
+    }
+}
+
+impl std::fmt::Display for Duration {
+    // 5400000 is 1h30m
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0 == 0 {
+            return write!(f, "0s");
+        }
+        let mut rest = self.0;
+        for (name, mult) in DURATION_UNITS.iter() {
+            if rest >= *mult {
+                write!(f, "{}{}", rest / mult, name)?;
+                rest %= mult;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Numeric for Duration {
+    fn zero() -> Self {
+        Duration(0)
+    }
+    fn name() -> &'static str {
+        "duration"
+    }
+    fn is_multiple_of(&self, step: &Self) -> bool {
+        step.0 == 0 || self.0.is_multiple_of(step.0)
+    }
+}
+
+impl UnitValue for Duration {
+    fn units(&self) -> u64 {
+        self.0
+    }
+    fn unit_name() -> &'static str {
+        "ms"
+    }
+    // every unit at most once, from bigger to smaller like the literals
+    fn pattern() -> &'static str {
+        "^([0-9]+d([0-9]+h)?([0-9]+m)?([0-9]+s)?([0-9]+ms)?|[0-9]+h([0-9]+m)?([0-9]+s)?([0-9]+ms)?|[0-9]+m([0-9]+s)?([0-9]+ms)?|[0-9]+s([0-9]+ms)?|[0-9]+ms)$"
+    }
+}
+
+// bytes and the multiplier of the unit the value was written with, 0 if there is none
+#[derive(Clone, Copy)]
+pub struct ByteSize(u64, u64);
+
+impl ByteSize {
+    pub fn from_bytes(val: u64) -> ByteSize {
+        ByteSize(val, 0)
+    }
+
+    pub fn with_unit(val: u64, unit: u64) -> ByteSize {
+        ByteSize(val, unit)
+    }
+}
+
+// sizes are compared in bytes, 1000B is 1KB
+impl PartialEq for ByteSize {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl PartialOrd for ByteSize {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl std::fmt::Display for ByteSize {
+    // the unit of the literal, otherwise the unit giving the smallest exact number: 65536 is 64KiB, 10000000 is 10MB
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some((name, mult)) = SIZE_UNITS.iter().find(|(_, mult)| *mult == self.1) {
+            return write!(f, "{}{}", self.0 / mult, name);
+        }
+        let best = SIZE_UNITS.iter()
+            .filter(|(_, mult)| self.0.is_multiple_of(*mult))
+            .min_by_key(|(_, mult)| self.0 / mult);
+        match best {
+            Some((name, mult)) if self.0 != 0 => write!(f, "{}{}", self.0 / mult, name),
+            _ => write!(f, "{}B", self.0),
+        }
+    }
+}
+
+impl Numeric for ByteSize {
+    fn zero() -> Self {
+        ByteSize(0, 0)
+    }
+    fn name() -> &'static str {
+        "size"
+    }
+    fn is_multiple_of(&self, step: &Self) -> bool {
+        step.0 == 0 || self.0.is_multiple_of(step.0)
+    }
+}
+
+impl UnitValue for ByteSize {
+    fn units(&self) -> u64 {
+        self.0
+    }
+    fn unit_name() -> &'static str {
+        "bytes"
+    }
+    fn pattern() -> &'static str {
+        "^[0-9]+(B|KB|MB|GB|TB|KiB|MiB|GiB|TiB)$"
     }
 }
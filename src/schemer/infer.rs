@@ -0,0 +1,262 @@
+use super::objects::*;
+use super::object_base::ObjectBase;
+use super::helpers::*;
+use super::json::JsonValue;
+use super::decimal::Float;
+use super::datetime::{Date, Time, DateTime};
+
+// word-like strings with up to this many different values that repeat are enums
+const MAX_ENUM_VALUES: usize = 5;
+const MAX_ENUM_VALUE_LEN: usize = 32;
+
+#[derive(Clone, Copy, PartialEq)]
+enum TextKind {
+    String,
+    Date,
+    Time,
+    DateTime,
+}
+
+impl TextKind {
+    fn of(val: &str) -> TextKind {
+        if Date::parse(val).is_ok() {
+            TextKind::Date
+        } else if DateTime::parse(val).is_ok() {
+            TextKind::DateTime
+        } else if Time::parse(val).is_ok() {
+            TextKind::Time
+        } else {
+            TextKind::String
+        }
+    }
+}
+
+// what the samples have in a place of the document
+#[derive(Clone)]
+enum Shape {
+    Null,
+    Boolean,
+    Integer,
+    Floating,
+    // the kind, different values (up to MAX_ENUM_VALUES + 1) and how many values were seen
+    Text(TextKind, Vec<String>, usize),
+    Array(Box<Shape>),
+    // fields with the number of objects that have them, and the number of objects
+    Object(OrderedMap<(Shape, usize)>, usize),
+    Any,
+}
+
+impl Shape {
+    fn of(val: &JsonValue) -> Shape {
+        match val {
+            JsonValue::Null => Shape::Null,
+            JsonValue::Boolean(_) => Shape::Boolean,
+            JsonValue::Number(lit) => if lit.parse::<i64>().is_ok() { Shape::Integer } else { Shape::Floating },
+            JsonValue::String(s) => Shape::Text(TextKind::of(s), vec![s.clone()], 1),
+            JsonValue::Array(items) => {
+                Shape::Array(Box::new(items.iter().fold(Shape::Null, |acc, v| acc.merge(Shape::of(v)))))
+            },
+            JsonValue::Object(fields) => {
+                let mut res = OrderedMap::new();
+                for (k, v) in fields.iter() {
+                    res.insert(k.clone(), (Shape::of(v), 1));
+                }
+                Shape::Object(res, 1)
+            },
+        }
+    }
+
+    // integers widen to floating, different kinds of values become `any`
+    fn merge(self, other: Shape) -> Shape {
+        match (self, other) {
+            (Shape::Null, other) | (other, Shape::Null) => other,
+            (Shape::Boolean, Shape::Boolean) => Shape::Boolean,
+            (Shape::Integer, Shape::Integer) => Shape::Integer,
+            (Shape::Integer, Shape::Floating)
+            | (Shape::Floating, Shape::Integer)
+            | (Shape::Floating, Shape::Floating) => Shape::Floating,
+            (Shape::Text(kind, mut values, count), Shape::Text(other_kind, other_values, other_count)) => {
+                for v in other_values {
+                    if values.len() <= MAX_ENUM_VALUES && !values.contains(&v) {
+                        values.push(v);
+                    }
+                }
+                let kind = if kind == other_kind { kind } else { TextKind::String };
+                Shape::Text(kind, values, count + other_count)
+            },
+            (Shape::Array(items), Shape::Array(other_items)) => Shape::Array(Box::new(items.merge(*other_items))),
+            (Shape::Object(mut fields, count), Shape::Object(other_fields, other_count)) => {
+                for (k, (shape, seen)) in other_fields.iter() {
+                    let merged = match fields.get(k) {
+                        Some((old, old_seen)) => (old.clone().merge(shape.clone()), old_seen + seen),
+                        None => (shape.clone(), *seen),
+                    };
+                    fields.insert(k.clone(), merged);
+                }
+                Shape::Object(fields, count + other_count)
+            },
+            _ => Shape::Any,
+        }
+    }
+
+    // `prod`, `staging`, `eu-1`, but not host names or free text
+    fn is_enum(values: &[String], count: usize) -> bool {
+        let is_word = |v: &String| !v.is_empty() && v.len() <= MAX_ENUM_VALUE_LEN
+            && v.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        values.len() >= 2 && values.len() <= MAX_ENUM_VALUES && count > values.len() && values.iter().all(is_word)
+    }
+
+    // the element with the value of the sample
+    fn element(&self, sample: Option<&JsonValue>) -> Element {
+        let sample = match sample {
+            Some(JsonValue::Null) => None,
+            other => other,
+        };
+        match self {
+            Shape::Null | Shape::Any => {
+                let mut val = AnyType::new();
+                if let Some(v) = sample {
+                    val.add_value(Shape::of(v).element(Some(v)));
+                }
+                Element::Any(val)
+            },
+            Shape::Boolean => match sample.and_then(|v| v.as_bool()) {
+                Some(v) => Element::Boolean(BooleanType::from(v)),
+                None => Element::Boolean(BooleanType::new()),
+            },
+            Shape::Integer => match sample.and_then(|v| v.as_i64()) {
+                Some(v) => Element::Integer(IntegerType::from(v)),
+                None => Element::Integer(IntegerType::new()),
+            },
+            Shape::Floating => match sample.and_then(|v| v.as_number()).and_then(|v| Float::parse(v).ok()) {
+                Some(v) => Element::Floating(FloatingType::from(v)),
+                None => Element::Floating(FloatingType::new()),
+            },
+            Shape::Text(kind, values, count) => {
+                let text = sample.and_then(|v| v.as_str());
+                match kind {
+                    TextKind::Date => Element::Date(Shape::parsed(text, Date::parse)),
+                    TextKind::Time => Element::Time(Shape::parsed(text, Time::parse)),
+                    TextKind::DateTime => Element::DateTime(Shape::parsed(text, DateTime::parse)),
+                    TextKind::String => {
+                        let mut val = StringType::new();
+                        if Shape::is_enum(values, *count) {
+                            for v in values {
+                                val.add_enum_value(v);
+                            }
+                        }
+                        if let Some(v) = text {
+                            val.add_value(v);
+                        }
+                        Element::String(val)
+                    },
+                }
+            },
+            // arrays of arrays have no type, they are kept as `any` values
+            Shape::Array(items) if matches!(**items, Shape::Array(_)) => {
+                let mut val = AnyType::new_array();
+                for item in sample.and_then(|v| v.as_array()).map(|v| v.as_slice()).unwrap_or_default() {
+                    val.add_value(items.element(Some(item)));
+                }
+                Element::Any(val)
+            },
+            Shape::Array(items) => {
+                let values = sample.and_then(|v| v.as_array()).map(|v| v.iter().map(|item| items.element(Some(item))).collect());
+                Shape::array_of(items.element(None), values.unwrap_or_default())
+            },
+            Shape::Object(fields, count) => {
+                let mut obj = ObjectType::new();
+                for (name, (shape, seen)) in fields.iter() {
+                    let mut opts = Options::new();
+                    // fields that every sample has
+                    if seen == count {
+                        opts.add("required", Element::Boolean(BooleanType::from(true)));
+                    }
+                    let value = sample.and_then(|v| v.get(name));
+                    obj.add_field(FieldType::new(name.clone(), shape.element(value), opts));
+                }
+                Element::Object(obj)
+            },
+        }
+    }
+
+    fn parsed<T: Numeric>(text: Option<&str>, parse: fn(&str) -> Result<T, String>) -> NumberType<T> {
+        match text.and_then(|v| parse(v).ok()) {
+            Some(v) => NumberType::from(v),
+            None => NumberType::new(),
+        }
+    }
+
+    // `base` as an array with the values of the `values` elements
+    fn array_of(base: Element, values: Vec<Element>) -> Element {
+        fn fill<T: Numeric>(mut arr: NumberType<T>, values: Vec<Element>, get: fn(Element) -> Option<NumberType<T>>) -> NumberType<T> {
+            arr.make_array();
+            for v in values.into_iter().filter_map(get) {
+                if let Some(val) = v.value().as_value() {
+                    arr.add_value(val.clone());
+                }
+            }
+            arr
+        }
+        match base {
+            Element::Boolean(mut arr) => {
+                arr.make_array();
+                for v in values {
+                    if let Element::Boolean(val) = v {
+                        arr.add_value(val.get_value());
+                    }
+                }
+                Element::Boolean(arr)
+            },
+            Element::String(mut arr) => {
+                arr.make_array();
+                for v in values {
+                    if let Element::String(val) = v {
+                        if let Some(s) = val.value().as_value() {
+                            arr.add_value(s);
+                        }
+                    }
+                }
+                Element::String(arr)
+            },
+            Element::Object(mut arr) => {
+                arr.make_array();
+                for v in values {
+                    if let Element::Object(val) = v {
+                        arr.add_value(val);
+                    }
+                }
+                Element::Object(arr)
+            },
+            Element::Integer(arr) => Element::Integer(fill(arr, values, |v| match v { Element::Integer(v) => Some(v), _ => None })),
+            Element::Floating(arr) => Element::Floating(fill(arr, values, |v| match v { Element::Floating(v) => Some(v), _ => None })),
+            Element::Date(arr) => Element::Date(fill(arr, values, |v| match v { Element::Date(v) => Some(v), _ => None })),
+            Element::Time(arr) => Element::Time(fill(arr, values, |v| match v { Element::Time(v) => Some(v), _ => None })),
+            Element::DateTime(arr) => Element::DateTime(fill(arr, values, |v| match v { Element::DateTime(v) => Some(v), _ => None })),
+            _ => {
+                let mut arr = AnyType::new_array();
+                for v in values {
+                    arr.add_value(v);
+                }
+                Element::Any(arr)
+            },
+        }
+    }
+}
+
+// a module that all the samples fit, the values are from the first one
+pub fn infer_module(samples: &[JsonValue]) -> Module {
+    let mut module = Module::new();
+    let shape = samples.iter().fold(Shape::Null, |acc, v| acc.merge(Shape::of(v)));
+    let first = samples.first();
+    match shape.element(first) {
+        // top-level fields of the samples are the fields of the module
+        Element::Object(obj) => {
+            for (_, fld) in obj.fields().iter() {
+                module.add_field(fld.clone());
+            }
+        },
+        element => module.add_field(FieldType::new(String::from("main"), element, Options::new())),
+    }
+    module
+}
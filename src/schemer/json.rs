@@ -0,0 +1,312 @@
+use super::scanner::Scanner;
+use super::helpers::OrderedMap;
+
+// a json document. Numbers keep their literals, so big and precise values are not rounded
+#[derive(Clone)]
+pub enum JsonValue {
+    Null,
+    Boolean(bool),
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(OrderedMap<JsonValue>),
+}
+
+mod utils {
+    use super::super::scanner::Scanner;
+
+    pub fn error(scan: &Scanner, msg: &str) -> String {
+        let pos = scan.position();
+        format!("{} at {}:{}", msg, pos.0, pos.1)
+    }
+
+    pub fn skip_spaces(scan: &mut Scanner) {
+        scan.advance_while(|c| c == ' ' || c == '\t' || c == '\n' || c == '\r');
+    }
+
+    pub fn scan_hex4(scan: &mut Scanner) -> Option<u32> {
+        let digits = scan.get().get(0..4)?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        scan.jump(4);
+        u32::from_str_radix(digits, 16).ok()
+    }
+
+    // \uXXXX, pairs of surrogates are joined
+    pub fn scan_unicode(scan: &mut Scanner) -> Result<char, String> {
+        let invalid = error(scan, "Invalid unicode escape");
+        let high = scan_hex4(scan).ok_or_else(|| invalid.clone())?;
+        let code = match high {
+            0xD800..=0xDBFF => {
+                if !scan.get().starts_with("\\u") {
+                    return Err(invalid);
+                }
+                scan.jump(2);
+                match scan_hex4(scan) {
+                    Some(low @ 0xDC00..=0xDFFF) => 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00),
+                    _ => return Err(invalid),
+                }
+            },
+            _ => high,
+        };
+        char::from_u32(code).ok_or(invalid)
+    }
+
+    pub fn is_digit(c: char) -> bool {
+        c.is_ascii_digit()
+    }
+}
+
+struct JsonParser<'a> {
+    scan: Scanner<'a>,
+    depth: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    // deep documents would overflow the stack
+    const MAX_DEPTH: usize = 512;
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        utils::skip_spaces(&mut self.scan);
+        if self.scan.top() == c && !self.scan.eol() {
+            self.scan.advance();
+            Ok(())
+        } else {
+            Err(utils::error(&self.scan, &format!("Expected '{}'", c)))
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<JsonValue, String> {
+        utils::skip_spaces(&mut self.scan);
+        if self.scan.eol() {
+            return Err(utils::error(&self.scan, "Unexpected end of the document"));
+        }
+        match self.scan.top() {
+            '{' | '[' if self.depth >= JsonParser::MAX_DEPTH => {
+                Err(utils::error(&self.scan, "The document is nested too deep"))
+            },
+            '{' => self.parse_object(),
+            '[' => self.parse_array(),
+            '"' => Ok(JsonValue::String(self.parse_string()?)),
+            '-' | '0'..='9' => self.parse_number(),
+            _ => self.parse_literal(),
+        }
+    }
+
+    fn parse_literal(&mut self) -> Result<JsonValue, String> {
+        for (name, value) in [("null", JsonValue::Null), ("true", JsonValue::Boolean(true)), ("false", JsonValue::Boolean(false))] {
+            if self.scan.get().starts_with(name) {
+                self.scan.jump(name.len());
+                return Ok(value);
+            }
+        }
+        Err(utils::error(&self.scan, "Expected a value"))
+    }
+
+    // -?(0|[1-9][0-9]*)(.[0-9]+)?([eE][+-]?[0-9]+)?
+    fn parse_number(&mut self) -> Result<JsonValue, String> {
+        let begin = self.scan.get();
+        let mut len = 0;
+        if self.scan.top() == '-' {
+            len += self.scan.advance();
+        }
+        match self.scan.top() {
+            '0' => {
+                len += self.scan.advance();
+                if utils::is_digit(self.scan.top()) {
+                    return Err(utils::error(&self.scan, "Leading zeros are not allowed"));
+                }
+            },
+            '1'..='9' => len += self.scan.advance_while(utils::is_digit),
+            _ => return Err(utils::error(&self.scan, "Expected digits")),
+        }
+        if self.scan.top() == '.' {
+            len += self.scan.advance();
+            let digits = self.scan.advance_while(utils::is_digit);
+            if digits == 0 {
+                return Err(utils::error(&self.scan, "Expected digits after '.'"));
+            }
+            len += digits;
+        }
+        if self.scan.top() == 'e' || self.scan.top() == 'E' {
+            len += self.scan.advance();
+            if self.scan.top() == '+' || self.scan.top() == '-' {
+                len += self.scan.advance();
+            }
+            let digits = self.scan.advance_while(utils::is_digit);
+            if digits == 0 {
+                return Err(utils::error(&self.scan, "Expected digits of the exponent"));
+            }
+            len += digits;
+        }
+        Ok(JsonValue::Number(begin[..len].to_string()))
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        let begin = utils::error(&self.scan, "Unterminated string");
+        self.scan.advance();
+        let mut result = String::new();
+        loop {
+            if self.scan.eol() {
+                return Err(begin);
+            }
+            match self.scan.top() {
+                '"' => break,
+                '\\' => {
+                    self.scan.advance();
+                    let c = match self.scan.top() {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        '/' => '/',
+                        '"' => '"',
+                        '\\' => '\\',
+                        'u' => {
+                            self.scan.advance();
+                            result.push(utils::scan_unicode(&mut self.scan)?);
+                            continue;
+                        },
+                        _ => return Err(utils::error(&self.scan, "Invalid escape")),
+                    };
+                    result.push(c);
+                },
+                c if (c as u32) < 0x20 => return Err(utils::error(&self.scan, "Control characters should be escaped")),
+                c => result.push(c),
+            }
+            self.scan.advance();
+        }
+        self.scan.advance();
+        Ok(result)
+    }
+
+    // `close` after `open`, values are separated with commas
+    fn parse_items<F>(&mut self, close: char, mut item: F) -> Result<(), String>
+        where F: FnMut(&mut JsonParser<'a>) -> Result<(), String> {
+        self.scan.advance();
+        self.depth += 1;
+        utils::skip_spaces(&mut self.scan);
+        if self.scan.top() == close {
+            self.scan.advance();
+            self.depth -= 1;
+            return Ok(());
+        }
+        loop {
+            item(self)?;
+            utils::skip_spaces(&mut self.scan);
+            match self.scan.top() {
+                ',' => { self.scan.advance(); },
+                c if c == close && !self.scan.eol() => {
+                    self.scan.advance();
+                    self.depth -= 1;
+                    return Ok(());
+                },
+                _ => return Err(utils::error(&self.scan, &format!("Expected ',' or '{}'", close))),
+            }
+        }
+    }
+
+    // the last value of a repeated key wins
+    fn parse_object(&mut self) -> Result<JsonValue, String> {
+        let mut result = OrderedMap::new();
+        self.parse_items('}', |parser| {
+            utils::skip_spaces(&mut parser.scan);
+            if parser.scan.top() != '"' {
+                return Err(utils::error(&parser.scan, "Expected a key"));
+            }
+            let key = parser.parse_string()?;
+            parser.expect(':')?;
+            result.insert(key, parser.parse_value()?);
+            Ok(())
+        })?;
+        Ok(JsonValue::Object(result))
+    }
+
+    fn parse_array(&mut self) -> Result<JsonValue, String> {
+        let mut result = Vec::new();
+        self.parse_items(']', |parser| {
+            result.push(parser.parse_value()?);
+            Ok(())
+        })?;
+        Ok(JsonValue::Array(result))
+    }
+}
+
+impl JsonValue {
+    pub fn parse(data: &str) -> Result<JsonValue, String> {
+        let mut parser = JsonParser { scan: Scanner::new(data), depth: 0 };
+        let value = parser.parse_value()?;
+        utils::skip_spaces(&mut parser.scan);
+        if !parser.scan.eol() {
+            return Err(utils::error(&parser.scan, "Unexpected data after the document"));
+        }
+        Ok(value)
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            JsonValue::Null => "null",
+            JsonValue::Boolean(_) => "boolean",
+            JsonValue::Number(_) => "number",
+            JsonValue::String(_) => "string",
+            JsonValue::Array(_) => "array",
+            JsonValue::Object(_) => "object",
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Boolean(val) => Some(*val),
+            _ => None,
+        }
+    }
+
+    // the number literal: 10, -1.5e3
+    pub fn as_number(&self) -> Option<&str> {
+        match self {
+            JsonValue::Number(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    // numbers without a fraction and an exponent that fit i64
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_number()?.parse::<i64>().ok()
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&OrderedMap<JsonValue>> {
+        match self {
+            JsonValue::Object(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        self.as_object()?.get(key)
+    }
+}
+
+// a reference token of a json pointer (RFC 6901): `~` is `~0`, `/` is `~1`
+pub fn pointer_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+pub fn pointer_unescape(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
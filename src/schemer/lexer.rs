@@ -23,85 +23,254 @@ pub struct Lexer {
 
 mod helpers {
     use super::Scanner;
+    use super::{Token, TokenInfo};
+    use super::super::helpers::{DURATION_UNITS, SIZE_UNITS, unit_multiplier};
+    use super::super::datetime::{literal_len, Date, Time, DateTime};
+    use super::super::semver::{self, Version};
+    use super::super::bigint::BigInt;
+    use super::super::decimal::Float;
+    use std::convert::TryFrom;
     pub enum Number {
         Integer(i64),
-        Floating(f64),
+        BigInteger(BigInt),
+        Floating(Float),
     }
 
-    pub fn scan_integer(scan: &mut Scanner, base: u32) -> i64 {
-        let mut d: i64 = 0;
-        while !scan.eol() && scan.top().is_digit(base) {
-            d *= base as i64;
-            d += scan.top().to_digit(base).unwrap() as i64;
-            scan.advance();
+    impl Number {
+        pub fn to_u64(&self) -> Option<u64> {
+            match self {
+                Number::Integer(v) => u64::try_from(*v).ok(),
+                Number::BigInteger(v) => v.to_u64(),
+                Number::Floating(_) => None,
+            }
+        }
+
+        pub fn into_token(self) -> Token {
+            match self {
+                Number::Integer(v) => Token::Integer(v),
+                Number::BigInteger(v) => Token::BigInteger(v),
+                Number::Floating(v) => Token::Floating(v),
+            }
         }
-        return d;
     }
 
-    pub fn scan_number(scan: &mut Scanner) -> Number {
+    // `_` separates digits: 1_000_000, 0xFFFF_FFFF
+    fn scan_digits(scan: &mut Scanner, base: u32) -> Vec<u32> {
+        let mut digits = Vec::new();
+        while !scan.eol() {
+            if scan.top() == '_' && !digits.is_empty() && scan.next().is_digit(base) {
+                scan.advance();
+            }
+            match scan.top().to_digit(base) {
+                Some(val) => digits.push(val),
+                None => break,
+            }
+            scan.advance();
+        }
+        digits
+    }
 
+    // values that don't fit i64 are kept exactly as big integers
+    fn integer_number(digits: &[u32], base: u32) -> Number {
         let mut d: i64 = 0;
-        let mut a: f64 = 0.0;
-        let mut e: i64 = 0;
-        
-        while !scan.eol() && scan.top().is_digit(10) {
-            let value = scan.top().to_digit(10).unwrap();
-            
-            d *= 10;
-            d += value as i64;
-    
-            a *= 10.0;
-            a += value as f64;
+        for v in digits {
+            match d.checked_mul(base as i64).and_then(|x| x.checked_add(*v as i64)) {
+                Some(x) => d = x,
+                None => return Number::BigInteger(BigInt::from_digits(digits, base)),
+            }
+        }
+        Number::Integer(d)
+    }
+
+    // 0x, 0o and 0b numbers. Hex ones can be floats with a binary exponent: 0x1.8p3
+    pub fn scan_prefixed(scan: &mut Scanner, base: u32) -> Result<Number, String> {
+        let begin = scan.get();
+        let digits = scan_digits(scan, base);
+        if digits.is_empty() {
+            return Err(String::from("Expected digits after the number prefix"));
+        }
+        if base != 16 {
+            return Ok(integer_number(&digits, base));
+        }
+        let scan_bu = scan.backup();
+        let mut mantissa = digits.iter().fold(0.0, |acc, d| acc * 16.0 + *d as f64);
+        let mut exp: i64 = 0;
+        if scan.top() == '.' && scan.next().is_ascii_hexdigit() {
             scan.advance();
+            for d in scan_digits(scan, 16) {
+                mantissa = mantissa * 16.0 + d as f64;
+                exp -= 4;
+            }
         }
-    
+        if scan.top() != 'p' && scan.top() != 'P' {
+            scan.restore(&scan_bu);
+            return Ok(integer_number(&digits, base));
+        }
+        scan.advance();
+        let sign = match scan.top() {
+            '-' => { scan.advance(); -1 },
+            '+' => { scan.advance(); 1 },
+            _ => 1,
+        };
+        let power = scan_digits(scan, 10);
+        if power.is_empty() {
+            return Err(String::from("Expected the exponent of the hex floating"));
+        }
+        exp += sign * power.iter().fold(0_i64, |acc, d| (acc * 10 + *d as i64).min(100_000));
+        let value = mantissa * 2.0_f64.powi(exp.clamp(-100_000, 100_000) as i32);
+        // the literal is written with the prefix
+        let literal = format!("0x{}", &begin[..begin.len() - scan.get().len()]);
+        Float::with_literal(value, &literal).map(Number::Floating)
+    }
+
+    // `_` goes only between two digits, 1__0 and 1_ are errors at the underscore
+    pub fn check_underscores(data: &str, pos: (usize, usize)) -> Result<(), String> {
+        let hex = data.starts_with("0x") || data.starts_with("0X");
+        let is_digit = |c: &char| if hex { c.is_ascii_hexdigit() } else { c.is_ascii_digit() };
+        let chars = data.chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '.')
+            .collect::<Vec<char>>();
+        let misplaced = (0..chars.len()).find(|&i| {
+            chars[i] == '_' && !(i > 0 && is_digit(&chars[i - 1]) && chars.get(i + 1).is_some_and(is_digit))
+        });
+        match misplaced {
+            Some(i) => Err(format!("misplaced '_' in number literal at {}:{}", pos.0, pos.1 + i)),
+            None => Ok(()),
+        }
+    }
+
+    // numbers, durations and sizes
+    pub fn number_token(scan: &mut Scanner, pos: (usize, usize)) -> Result<TokenInfo, String> {
+        check_underscores(scan.get(), pos)?;
+        let num = scan_number(scan)
+            .map_err(|e| format!("{} at {}:{}", e, pos.0, pos.1))?;
+        let tok = match num {
+            Number::Floating(f) => Token::Floating(f),
+            _ => integer_token(scan, num)
+                .map_err(|e| format!("{} at {}:{}", e, pos.0, pos.1))?,
+        };
+        Ok(TokenInfo::new(tok, pos))
+    }
+
+    pub fn scan_number(scan: &mut Scanner) -> Result<Number, String> {
+        let begin = scan.get();
+        let digits = scan_digits(scan, 10);
         let mut found = false;
-        let scan_bu = scan.backup(); 
-    
-        if scan.top() == '.' {
+
+        // `1..5` is an interval, so the dot needs a digit after it
+        if scan.top() == '.' && scan.next().is_ascii_digit() {
             scan.advance();
-            while !scan.eol() && scan.top().is_digit(10) {
-                found = true;
-                a *= 10.0;
-                a += scan.top().to_digit(10).unwrap() as f64;
-                e -= 1;
-                scan.advance();
-            }
+            scan_digits(scan, 10);
+            found = true;
         }
-    
+
         if scan.top() == 'e' || scan.top() == 'E' {
-            let mut sign: i64 = 1;
-            let mut i: i64 = 0;
+            let scan_bu = scan.backup();
             scan.advance();
-            match scan.top() {
-                '+' => { scan.advance(); },
-                '-' => { scan.advance(); sign = -1 },
-                 _  => { }
+            if scan.top() == '+' || scan.top() == '-' {
+                scan.advance();
             }
-            while !scan.eol() && scan.top().is_digit(10) {
+            if !scan_digits(scan, 10).is_empty() {
                 found = true;
-                i *= 10;
-                i += scan.top().to_digit(10).unwrap() as i64;
-                scan.advance();
+            } else {
+                scan.restore(&scan_bu);
             }
-            e += i * sign;
         }
-    
-        while e > 0 {
-            a *= 10.0;
-            e -= 1;
+
+        if found {
+            Ok(Number::Floating(Float::parse(&begin[..begin.len() - scan.get().len()])?))
+        } else {
+            Ok(integer_number(&digits, 10))
         }
-    
-        while e < 0 {
-            a *= 0.1;
-            e += 1;
+    }
+
+    fn scan_unit_name(scan: &mut Scanner) -> String {
+        let base = scan.backup();
+        let shift = scan.advance_while(|c| { c.is_ascii_alphabetic() });
+        String::from(&base.get()[0..shift])
+    }
+
+    fn apply_unit(value: Option<u64>, mult: u64, total: u64) -> Result<u64, String> {
+        match value.and_then(|v| v.checked_mul(mult)).and_then(|v| v.checked_add(total)) {
+            Some(v) => Ok(v),
+            None => Err(String::from("Value with units is too big")),
+        }
+    }
+
+    // 30s, 1h30m, 64KiB. None if the number has no known unit 
+    pub fn scan_units(scan: &mut Scanner, value: Option<u64>) -> Result<Option<Token>, String> {
+        let backup = scan.backup();
+        let mut unit = scan_unit_name(scan);
+        if let Some(mult) = unit_multiplier(&SIZE_UNITS, &unit) {
+            if is_ident(scan.top()) || scan.top().is_ascii_digit() {
+                scan.restore(&backup);
+                return Ok(None);
+            }
+            return Ok(Some(Token::Size(apply_unit(value, mult, 0)?, mult)));
+        }
+        let mut value = value;
+        let mut total: u64 = 0;
+        let mut last = u64::MAX;
+        loop {
+            match unit_multiplier(&DURATION_UNITS, &unit) {
+                // units of a duration go from bigger to smaller: 1h30m, not 30m1h
+                Some(mult) if mult < last => {
+                    total = apply_unit(value, mult, total)?;
+                    last = mult;
+                },
+                _ => {
+                    scan.restore(&backup);
+                    return Ok(None);
+                },
+            }
+            if !scan.top().is_ascii_digit() {
+                break;
+            }
+            let digits = scan_digits(scan, 10);
+            value = integer_number(&digits, 10).to_u64();
+            unit = scan_unit_name(scan);
+        }
+        if is_ident(scan.top()) {
+            scan.restore(&backup);
+            return Ok(None);
+        }
+        Ok(Some(Token::Duration(total)))
+    }
+
+    pub fn integer_token(scan: &mut Scanner, value: Number) -> Result<Token, String> {
+        match scan_units(scan, value.to_u64())? {
+            Some(tok) => Ok(tok),
+            None => Ok(value.into_token()),
+        }
+    }
+
+    // 2020-01-01, 12:30:00, 2020-01-01T12:30:00Z. None if there is no such literal
+    pub fn scan_date_time(scan: &mut Scanner) -> Result<Option<Token>, String> {
+        let len = literal_len(scan.get());
+        if len == 0 {
+            return Ok(None);
         }
-        return if found {
-            Number::Floating(a)
+        let literal = &scan.get()[0..len];
+        let token = if literal.len() == 10 && literal.as_bytes()[4] == b'-' {
+            Token::Date(Date::parse(literal)?)
+        } else if literal.len() > 10 && literal.as_bytes()[4] == b'-' {
+            Token::DateTime(DateTime::parse(literal)?)
         } else {
-            scan.restore(&scan_bu);
-            Number::Integer(d)
+            Token::Time(Time::parse(literal)?)
+        };
+        scan.jump(len);
+        Ok(Some(token))
+    }
+
+    // 1.2.0, 1.0.0-alpha.1+build.5. None if there is no such literal
+    pub fn scan_version(scan: &mut Scanner) -> Result<Option<Token>, String> {
+        let len = semver::literal_len(scan.get());
+        if len == 0 {
+            return Ok(None);
         }
+        let token = Token::Version(Version::parse(&scan.get()[0..len])?);
+        scan.jump(len);
+        Ok(Some(token))
     }
 
     pub fn str_head_tail(data: &str) -> (char, &str) {
@@ -112,34 +281,77 @@ mod helpers {
     }
 
     pub fn is_ident(c: char) -> bool {
-        return c.is_ascii_alphabetic() || c == '_';
+        c.is_ascii_alphabetic() || c == '_'
     }
 
     pub fn scan_ident(scanner: &mut Scanner) -> String {
         let base = scanner.backup();
-        let shift = scanner.advance_while(|c| { is_ident(c) || c.is_digit(10) });
-        return String::from(&base.get()[0..shift])
+        let shift = scanner.advance_while(|c| { is_ident(c) || c.is_ascii_digit() });
+        String::from(&base.get()[0..shift])
+    }
+
+    fn scan_hex4(scan: &mut Scanner) -> Option<u32> {
+        let digits = scan.get().get(0..4)?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        scan.jump(4);
+        u32::from_str_radix(digits, 16).ok()
     }
 
-    pub fn scan_string(scan: &mut Scanner, ending: &str) -> String {
+    // \uXXXX, pairs of surrogates are joined: \uD83D\uDE00
+    fn scan_unicode(scan: &mut Scanner) -> Result<char, String> {
+        let pos = scan.position();
+        let invalid = || format!("Invalid unicode escape at {}:{}", pos.0, pos.1);
+        let high = scan_hex4(scan).ok_or_else(invalid)?;
+        let code = match high {
+            0xD800..=0xDBFF => {
+                if !scan.get().starts_with("\\u") {
+                    return Err(invalid());
+                }
+                scan.jump(2);
+                match scan_hex4(scan) {
+                    Some(low @ 0xDC00..=0xDFFF) => 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00),
+                    _ => return Err(invalid()),
+                }
+            },
+            _ => high,
+        };
+        char::from_u32(code).ok_or_else(invalid)
+    }
+
+    // `begin` is the position of the opening quote. Raw strings have no escapes
+    pub fn scan_string(scan: &mut Scanner, ending: &str, raw: bool, begin: (usize, usize)) -> Result<String, String> {
 
         let mut result = String::new();
         let ec = ending.chars().next().unwrap_or('\0');
 
-        while !scan.eol() {
+        loop {
+            if scan.eol() {
+                return Err(format!("Unterminated string at {}:{}", begin.0, begin.1));
+            }
             if scan.get().starts_with(ending) {
                 scan.jump(ending.len());
                 break;
             }
             match scan.top() {
-                '\\' => {
+                '\\' if !raw => {
                     scan.advance();
                     match scan.top() {
                         'n' => result.push('\n'),
                         'r' => result.push('\r'),
                         't' => result.push('\t'),
+                        'b' => result.push('\u{8}'),
+                        'f' => result.push('\u{c}'),
+                        '/' => result.push('/'),
+                        '"' => result.push('"'),
                         '\\' => result.push('\\'),
-                        '\0' => result.push('\0'),
+                        '0' => result.push('\0'),
+                        'u' => {
+                            scan.advance();
+                            result.push(scan_unicode(scan)?);
+                            continue;
+                        },
                         val => if val == ec {
                                 result.push(ec);
                             } else {
@@ -153,16 +365,16 @@ mod helpers {
             scan.advance();
         }
 
-        return result;
+        Ok(result)
     }
 
     fn is_ident_string_rest(data: &str) -> bool {
-        data.chars().find(|&x| !(x.is_digit(10) || is_ident(x))).is_none()
+        data.chars().find(|&x| !(x.is_ascii_digit() || is_ident(x))).is_none()
     }
 
     pub fn is_ident_string(data: &str) -> bool {
         let (head, tail) = str_head_tail(data);
-        return is_ident(head) && is_ident_string_rest(tail);
+        is_ident(head) && is_ident_string_rest(tail)
     }
 
     pub fn skip_spaces(scanner: &mut Scanner) {
@@ -189,6 +401,7 @@ impl Lexer {
         lex.add_special(",", SpecialToken::Comma);
         lex.add_special(".", SpecialToken::Dot);
         lex.add_special("..", SpecialToken::Interval);
+        lex.add_special("<", SpecialToken::Less);
         lex.add_special(":", SpecialToken::Colon);
         lex.add_special(";", SpecialToken::Semicolon);
         lex.add_special("-", SpecialToken::Minus);
@@ -197,24 +410,47 @@ impl Lexer {
 
         lex.add_special("0x", SpecialToken::HexBegin);
         lex.add_special("0X", SpecialToken::HexBegin);
+        lex.add_special("0o", SpecialToken::OctBegin);
+        lex.add_special("0O", SpecialToken::OctBegin);
+        lex.add_special("0b", SpecialToken::BinBegin);
+        lex.add_special("0B", SpecialToken::BinBegin);
 
         lex.add_special("enum", SpecialToken::Enum);
         lex.add_special("null", SpecialToken::Null);
+        lex.add_special("step", SpecialToken::Step);
+        lex.add_special("within", SpecialToken::Within);
 
         lex.add_special("mod", SpecialToken::Mod);
         lex.add_special("alias", SpecialToken::Alias);
 
         lex.add_type("string", TypeName::TypeString);
         lex.add_type("integer", TypeName::TypeInteger);
+        lex.add_type("unsigned", TypeName::TypeUnsigned);
+        lex.add_type("u64", TypeName::TypeUnsigned);
+        lex.add_type("bigint", TypeName::TypeBigInt);
         lex.add_type("floating", TypeName::TypeFloating);
+        lex.add_type("decimal", TypeName::TypeDecimal);
         lex.add_type("boolean", TypeName::TypeBoolean);
         lex.add_type("object", TypeName::TypeObject);
         lex.add_type("any", TypeName::TypeAny);
+        lex.add_type("duration", TypeName::TypeDuration);
+        lex.add_type("size", TypeName::TypeSize);
+        lex.add_type("date", TypeName::TypeDate);
+        lex.add_type("time", TypeName::TypeTime);
+        lex.add_type("datetime", TypeName::TypeDateTime);
+        lex.add_type("ip", TypeName::TypeIp);
+        lex.add_type("ipv4", TypeName::TypeIpv4);
+        lex.add_type("ipv6", TypeName::TypeIpv6);
+        lex.add_type("cidr", TypeName::TypeCidr);
+        lex.add_type("endpoint", TypeName::TypeEndpoint);
+        lex.add_type("semver", TypeName::TypeSemver);
+        lex.add_type("bytes", TypeName::TypeBytes);
+        lex.add_type("path", TypeName::TypePath);
         
         lex.add("true", Token::Boolean(true));
         lex.add("false", Token::Boolean(false));
 
-        return lex;
+        lex
     }
 
     fn add(&mut self, key: &str, value: Token) {
@@ -244,7 +480,7 @@ impl Lexer {
             match next {
                 Some(expr) => {
                     let top = scanner.top();
-                    if expr.0.possible_ident && (is_ident(top) || top.is_digit(10)) {
+                    if expr.0.possible_ident && (is_ident(top) || top.is_ascii_digit()) {
                         let mut ival = String::from(&backup.get()[0..expr.1]);
                         ival.push_str(&scan_ident(&mut scanner));
                         result.push(TokenInfo::new(Token::Ident(ival), pos));
@@ -252,9 +488,24 @@ impl Lexer {
                         Token::Special(SpecialToken::Hash) => {
                             scanner.advance_while(|c| { c != '\n' });
                         },
-                        Token::Special(SpecialToken::HexBegin) => {
-                            let val = scan_integer(&mut scanner, 16);
-                            result.push(TokenInfo::new(Token::Integer(val), pos));
+                        Token::Special(SpecialToken::HexBegin) 
+                        | Token::Special(SpecialToken::OctBegin)
+                        | Token::Special(SpecialToken::BinBegin) => {
+                            let base = match &expr.0.value {
+                                Token::Special(SpecialToken::HexBegin) => 16,
+                                Token::Special(SpecialToken::OctBegin) => 8,
+                                _ => 2,
+                            };
+                            // `0B` without digits after it is a size
+                            if !scanner.top().is_digit(base) && backup.get().starts_with("0B") {
+                                scanner.restore(&backup);
+                                result.push(number_token(&mut scanner, pos)?);
+                                continue;
+                            }
+                            check_underscores(backup.get(), pos)?;
+                            let val = scan_prefixed(&mut scanner, base)
+                                .map_err(|e| format!("{} at {}:{}", e, pos.0, pos.1))?;
+                            result.push(TokenInfo::new(val.into_token(), pos));
                         },
                         _ => {
                             let mut found = TokenInfo::new(expr.0.value.clone(), pos);
@@ -266,21 +517,38 @@ impl Lexer {
                     }}
                 },
                 None => {
-                    if scanner.top() == '0' && scanner.next() != '.' {
-                        let num = scan_integer(&mut scanner, 8);
-                        result.push(TokenInfo::new(Token::Integer(num), pos));
-                    } else if scanner.top().is_digit(10) {
-                        let num = scan_number(&mut scanner);
-                        match num {
-                            Number::Integer(i) => result.push(TokenInfo::new(Token::Integer(i), pos)),
-                            Number::Floating(f) => result.push(TokenInfo::new(Token::Floating(f), pos)),
-                        }
+                    let literal = match scan_date_time(&mut scanner) {
+                        Ok(None) => scan_version(&mut scanner),
+                        other => other,
+                    }.map_err(|e| format!("{} at {}:{}", e, pos.0, pos.1))?;
+                    if let Some(tok) = literal {
+                        result.push(TokenInfo::new(tok, pos));
+                    } else if scanner.top() == '0' && (scanner.next().is_ascii_digit() || scanner.next() == '_') {
+                        return Err(format!("Leading zeros are not allowed, use 0o for octal numbers at {}:{}", 
+                            pos.0, pos.1));
+                    } else if scanner.top().is_ascii_digit() {
+                        result.push(number_token(&mut scanner, pos)?);
+                    } else if scanner.top() == 'r' && scanner.next() == '"' {
+                        // r"C:\path", no escapes
+                        scanner.jump(2);
+                        let svalue = scan_string(&mut scanner, "\"", true, pos)?;
+                        result.push(TokenInfo::new(Token::String(svalue), pos));
                     } else if is_ident(scanner.top()) {
                         let ident = scan_ident(&mut scanner);
                         result.push(TokenInfo::new(Token::Ident(ident), pos));
+                    } else if scanner.get().starts_with("\"\"\"") {
+                        // multiline, the new line right after the quotes is skipped
+                        scanner.jump(3);
+                        if scanner.get().starts_with("\r\n") {
+                            scanner.jump(2);
+                        } else if scanner.top() == '\n' {
+                            scanner.advance();
+                        }
+                        let svalue = scan_string(&mut scanner, "\"\"\"", false, pos)?;
+                        result.push(TokenInfo::new(Token::String(svalue), pos));
                     } else if scanner.top() == '"' {
                         scanner.advance();
-                        let svalue = scan_string(&mut scanner, "\"");
+                        let svalue = scan_string(&mut scanner, "\"", false, pos)?;
                         result.push(TokenInfo::new(Token::String(svalue), pos));
                     } else if !scanner.eol() {
                         return Err(format!("Unexpected character '{}' at {}:{}", 
@@ -289,7 +557,7 @@ impl Lexer {
                 },
             }
         }
-        return Ok(result);
+        Ok(result)
     }
 }
 
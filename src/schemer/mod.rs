@@ -6,8 +6,19 @@ pub mod tokens;
 pub mod objects;
 pub mod parser;
 pub mod helpers;
+pub mod datetime;
+pub mod network;
+pub mod semver;
+pub mod encoding;
+pub mod bigint;
+pub mod decimal;
 pub mod object_base;
 pub mod to_schemer;
 pub mod to_json;
+pub mod json;
+pub mod from_json_schema;
+pub mod infer;
+pub mod validate;
+pub mod overrides;
 pub mod formatting;
 pub mod environment;
@@ -0,0 +1,156 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum NetKind {
+    Ip,
+    Ipv4,
+    Ipv6,
+    Cidr,
+    Endpoint,
+}
+
+mod utils {
+    pub fn is_host_name(host: &str) -> bool {
+        host.len() <= 253 && host.split('.').all(|label| {
+            !label.is_empty() && label.len() <= 63
+                && !label.starts_with('-') && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+    }
+}
+
+// an address with the prefix length: 10.0.0.0/8
+#[derive(Clone, Copy, PartialEq)]
+pub struct Network {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl Network {
+    pub fn parse(data: &str) -> Option<Network> {
+        let (addr, prefix) = data.split_once('/')?;
+        let addr = IpAddr::from_str(addr).ok()?;
+        let prefix = prefix.parse::<u8>().ok()?;
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        if prefix > max {
+            return None;
+        }
+        Some(Network { addr, prefix })
+    }
+
+    fn bits(addr: &IpAddr) -> (u128, u32) {
+        match addr {
+            IpAddr::V4(v) => (u32::from(*v) as u128, 32),
+            IpAddr::V6(v) => (u128::from(*v), 128),
+        }
+    }
+
+    pub fn contains(&self, addr: &IpAddr) -> bool {
+        if self.addr.is_ipv4() != addr.is_ipv4() {
+            return false;
+        }
+        let (net, width) = Network::bits(&self.addr);
+        let (val, _) = Network::bits(addr);
+        let shift = width - self.prefix as u32;
+        shift >= 128 || (net >> shift) == (val >> shift)
+    }
+
+    // the address with the host bits cleared: 10.0.0.0/8 for 10.0.0.1/8
+    pub fn network(&self) -> Network {
+        let (val, width) = Network::bits(&self.addr);
+        let shift = width - self.prefix as u32;
+        let masked = if shift >= 128 { 0 } else { (val >> shift) << shift };
+        let addr = match self.addr {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::from(masked as u32)),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::from(masked)),
+        };
+        Network { addr, prefix: self.prefix }
+    }
+
+    pub fn contains_network(&self, other: &Network) -> bool {
+        other.prefix >= self.prefix && self.contains(&other.addr)
+    }
+}
+
+impl std::fmt::Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix)
+    }
+}
+
+impl NetKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            NetKind::Ip => "ip",
+            NetKind::Ipv4 => "ipv4",
+            NetKind::Ipv6 => "ipv6",
+            NetKind::Cidr => "cidr",
+            NetKind::Endpoint => "endpoint",
+        }
+    }
+
+    // checks the value and returns it in the canonical form: "0:0::1" is "::1"
+    pub fn normalize(&self, data: &str) -> Result<String, String> {
+        if let (NetKind::Cidr, Some(val)) = (self, Network::parse(data)) {
+            // a network with host bits is most likely a typo of the address or of the prefix
+            if val.network() != val {
+                return Err(format!("'{}' has host bits set, the network is {}", data, val.network()));
+            }
+        }
+        let result = match self {
+            NetKind::Ip => IpAddr::from_str(data).ok().map(|v| v.to_string()),
+            NetKind::Ipv4 => Ipv4Addr::from_str(data).ok().map(|v| v.to_string()),
+            NetKind::Ipv6 => Ipv6Addr::from_str(data).ok().map(|v| v.to_string()),
+            NetKind::Cidr => Network::parse(data).map(|v| v.to_string()),
+            NetKind::Endpoint => NetKind::normalize_endpoint(data),
+        };
+        match result {
+            Some(val) => Ok(val),
+            None => Err(format!("'{}' is not a valid {}", data, self.name())),
+        }
+    }
+
+    // host:port, [ipv6]:port
+    fn normalize_endpoint(data: &str) -> Option<String> {
+        let (host, port) = data.rsplit_once(':')?;
+        let port = port.parse::<u16>().ok()?;
+        if let Some(ip) = host.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            let addr = Ipv6Addr::from_str(ip).ok()?;
+            Some(format!("[{}]:{}", addr, port))
+        } else if let Ok(addr) = Ipv4Addr::from_str(host) {
+            Some(format!("{}:{}", addr, port))
+        } else if utils::is_host_name(host) {
+            Some(format!("{}:{}", host.to_ascii_lowercase(), port))
+        } else {
+            None
+        }
+    }
+
+    // `within` is checked for the addresses and the networks
+    pub fn is_within(&self, value: &str, network: &Network) -> bool {
+        match self {
+            NetKind::Ip | NetKind::Ipv4 | NetKind::Ipv6 => match IpAddr::from_str(value) {
+                Ok(addr) => network.contains(&addr),
+                Err(_) => false,
+            },
+            NetKind::Cidr => match Network::parse(value) {
+                Some(val) => network.contains_network(&val),
+                None => false,
+            },
+            NetKind::Endpoint => false,
+        }
+    }
+
+    pub fn has_within(&self) -> bool {
+        *self != NetKind::Endpoint
+    }
+
+    pub fn pattern(&self) -> Option<&'static str> {
+        match self {
+            NetKind::Cidr => Some("^([0-9.]+/[0-9]{1,2}|[0-9a-fA-F:.]+/[0-9]{1,3})$"),
+            NetKind::Endpoint => Some("^.+:[0-9]{1,5}$"),
+            _ => None,
+        }
+    }
+}
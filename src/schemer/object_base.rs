@@ -1,6 +1,8 @@
 
 use super::objects::*;
 use super::helpers::*;
+use super::network::NetKind;
+use super::encoding::Encoding;
 
 pub trait ObjectBase {
     fn create() -> Self;
@@ -14,15 +16,12 @@ impl ObjectBase for StringType {
         StringType::new()
     }
     fn is_array(&self) -> bool {
-        match self.value() {
-            PossibleArray::Array(_) => true,
-            _ => false,
-        }
+        matches!(self.value(), PossibleArray::Array(_))
     }
     fn is_default(&self) -> bool {
         match self.value() {
-            PossibleArray::Value(v) => v.len() == 0,
-            PossibleArray::Array(v) => v.len() == 0,
+            PossibleArray::Value(v) => v.is_empty(),
+            PossibleArray::Array(v) => v.is_empty(),
         }        
     }    
     fn make_array(&mut self) {
@@ -30,6 +29,60 @@ impl ObjectBase for StringType {
     }
 }
 
+impl ObjectBase for NetType {
+    fn create() -> Self {
+        NetType::new(NetKind::Ip)
+    }
+    fn is_array(&self) -> bool {
+        self.value().is_array()
+    }
+    fn is_default(&self) -> bool {
+        match self.value() {
+            PossibleArray::Value(v) => v.is_empty(),
+            PossibleArray::Array(v) => v.is_empty(),
+        }
+    }
+    fn make_array(&mut self) {
+        self.set_value(PossibleArray::Array(Vec::new()))
+    }
+}
+
+impl ObjectBase for BytesType {
+    fn create() -> Self {
+        BytesType::new(Encoding::Base64)
+    }
+    fn is_array(&self) -> bool {
+        self.value().is_array()
+    }
+    fn is_default(&self) -> bool {
+        match self.value() {
+            PossibleArray::Value(v) => v.is_empty(),
+            PossibleArray::Array(v) => v.is_empty(),
+        }
+    }
+    fn make_array(&mut self) {
+        self.set_value(PossibleArray::Array(Vec::new()))
+    }
+}
+
+impl ObjectBase for PathType {
+    fn create() -> Self {
+        PathType::new()
+    }
+    fn is_array(&self) -> bool {
+        self.value().is_array()
+    }
+    fn is_default(&self) -> bool {
+        match self.value() {
+            PossibleArray::Value(v) => v.is_empty(),
+            PossibleArray::Array(v) => v.is_empty(),
+        }
+    }
+    fn make_array(&mut self) {
+        self.set_value(PossibleArray::Array(Vec::new()))
+    }
+}
+
 impl ObjectBase for BooleanType {
     fn create() -> Self {
         BooleanType::new()
@@ -43,7 +96,7 @@ impl ObjectBase for BooleanType {
     fn is_default(&self) -> bool {
         match self.value() {
             PossibleArray::Value(v) => !*v,
-            PossibleArray::Array(v) => v.len() == 0,
+            PossibleArray::Array(v) => v.is_empty(),
         }
     }
 }
@@ -58,7 +111,7 @@ impl ObjectBase for ObjectType {
     fn is_default(&self) -> bool {
         match self.value() {
             PossibleArray::Value(v) => v.is_none(),
-            PossibleArray::Array(v) => v.len() == 0,
+            PossibleArray::Array(v) => v.is_empty(),
         }
     }    
     fn make_array(&mut self) {
@@ -76,7 +129,7 @@ impl<T> ObjectBase for NumberType<T> where T: Numeric, T: Clone {
     fn is_default(&self) -> bool {
         match self.value() {
             PossibleArray::Value(v) => *v == T::zero(),
-            PossibleArray::Array(v) => v.len() == 0,
+            PossibleArray::Array(v) => v.is_empty(),
         }
     }
     fn make_array(&mut self) {
@@ -1,6 +1,11 @@
-
 use std::collections::HashMap;
 use super::helpers::*;
+use super::datetime::{Date, Time, DateTime};
+use super::network::{NetKind, Network};
+use super::semver::Version;
+use super::encoding::Encoding;
+use super::bigint::BigInt;
+use super::decimal::{Float, Decimal};
 
 #[derive(Clone)]
 pub struct StringType {
@@ -30,7 +35,7 @@ impl StringType {
     
     pub fn check_enum(&self, val: &String) -> bool {
         match &self.enum_values {
-            Some(vals) => vals.check(&val),
+            Some(vals) => vals.check(val),
             None => true,
         }
     }
@@ -61,12 +66,232 @@ impl StringType {
             },
         }
     }
+
+    pub fn set_enum_label(&mut self, label: &str) {
+        if let Some(values) = &mut self.enum_values {
+            values.set_last_label(label);
+        }
+    }
+}
+
+// ip, ipv4, ipv6, cidr and endpoint. Values are kept as strings in the canonical form
+#[derive(Clone)]
+pub struct NetType {
+    kind: NetKind,
+    value: PossibleArray<String>,
+    enum_values: Option<Enum<String>>,
+    networks: Vec<Network>,
+}
+
+impl NetType {
+    pub fn new(kind: NetKind) -> NetType {
+        NetType {
+            kind,
+            value: PossibleArray::Value(String::new()),
+            enum_values: None,
+            networks: Vec::new(),
+        }
+    }
+
+    pub fn kind(&self) -> NetKind {
+        self.kind
+    }
+
+    pub fn value(&self) -> &PossibleArray<String> {
+        &self.value
+    }
+
+    pub fn set_value(&mut self, val: PossibleArray<String>) {
+        self.value = val;
+    }
+
+    pub fn add_value(&mut self, value: &str) {
+        self.value.add_value(String::from(value));
+    }
+
+    pub fn networks(&self) -> &Vec<Network> {
+        &self.networks
+    }
+
+    pub fn add_network(&mut self, val: Network) {
+        self.networks.push(val);
+    }
+
+    // a value should be within any of the networks
+    pub fn check_networks(&self, val: &str) -> bool {
+        self.networks.is_empty() || self.networks.iter().any(|n| self.kind.is_within(val, n))
+    }
+
+    pub fn check_enum(&self, val: &String) -> bool {
+        match &self.enum_values {
+            Some(vals) => vals.check(val),
+            None => true,
+        }
+    }
+
+    pub fn enum_values(&self) -> &Option<Enum<String>> {
+        &self.enum_values
+    }
+
+    pub fn add_enum_value(&mut self, val: &str) -> bool {
+        match &mut self.enum_values {
+            Some(values) => values.try_add(val.to_string()),
+            None => {
+                self.add_value(val);
+                self.enum_values = Some(Enum::create_with(val.to_string()));
+                true
+            },
+        }
+    }
+
+    pub fn set_enum_label(&mut self, label: &str) {
+        if let Some(values) = &mut self.enum_values {
+            values.set_last_label(label);
+        }
+    }
+
+    // the first enum value that is not within the networks
+    pub fn invalid_enum_value(&self) -> Option<String> {
+        match &self.enum_values {
+            Some(vals) => vals.values().iter().find(|v| !self.check_networks(v)).cloned(),
+            None => None,
+        }
+    }
+}
+
+// binary data written as text. The interval is the length of the decoded data
+#[derive(Clone)]
+pub struct BytesType {
+    encoding: Encoding,
+    value: PossibleArray<String>,
+    length: Interval<i64>,
+}
+
+impl BytesType {
+    pub fn new(encoding: Encoding) -> BytesType {
+        BytesType {
+            encoding,
+            value: PossibleArray::Value(String::new()),
+            length: Interval::none(),
+        }
+    }
+
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    pub fn value(&self) -> &PossibleArray<String> {
+        &self.value
+    }
+
+    pub fn set_value(&mut self, val: PossibleArray<String>) {
+        self.value = val;
+    }
+
+    pub fn add_value(&mut self, value: &str) {
+        self.value.add_value(String::from(value));
+    }
+
+    pub fn length(&self) -> &Interval<i64> {
+        &self.length
+    }
+
+    pub fn set_length(&mut self, val: Interval<i64>) {
+        self.length = val;
+    }
+
+    pub fn check_length(&self, len: usize) -> bool {
+        self.length.check(len as i64)
+    }
+}
+
+// a filesystem path with the rules from the field options
+#[derive(Clone)]
+pub struct PathType {
+    value: PossibleArray<String>,
+    absolute: bool,
+    relative: bool,
+    extension: Option<String>,
+    must_exist: bool,
+}
+
+impl PathType {
+    pub fn new() -> PathType {
+        PathType {
+            value: PossibleArray::Value(String::new()),
+            absolute: false,
+            relative: false,
+            extension: None,
+            must_exist: false,
+        }
+    }
+
+    pub fn value(&self) -> &PossibleArray<String> {
+        &self.value
+    }
+
+    pub fn set_value(&mut self, val: PossibleArray<String>) {
+        self.value = val;
+    }
+
+    pub fn add_value(&mut self, value: &str) {
+        self.value.add_value(String::from(value));
+    }
+
+    pub fn is_absolute(&self) -> bool {
+        self.absolute
+    }
+
+    pub fn set_absolute(&mut self) {
+        self.absolute = true;
+    }
+
+    pub fn is_relative(&self) -> bool {
+        self.relative
+    }
+
+    pub fn set_relative(&mut self) {
+        self.relative = true;
+    }
+
+    pub fn extension(&self) -> Option<&str> {
+        self.extension.as_deref()
+    }
+
+    // "pem" and ".pem" are the same extension
+    pub fn set_extension(&mut self, val: &str) {
+        let ext = if val.starts_with('.') { val.to_string() } else { format!(".{}", val) };
+        self.extension = Some(ext);
+    }
+
+    pub fn must_exist(&self) -> bool {
+        self.must_exist
+    }
+
+    pub fn set_must_exist(&mut self) {
+        self.must_exist = true;
+    }
+
+    // the rules that don't need the filesystem
+    pub fn check(&self, val: &str) -> Result<(), String> {
+        let path = std::path::Path::new(val);
+        if self.absolute && !path.is_absolute() {
+            Err(format!("Path '{}' should be absolute", val))
+        } else if self.relative && !path.is_relative() {
+            Err(format!("Path '{}' should be relative", val))
+        } else if self.extension.as_ref().is_some_and(|ext| !val.ends_with(ext.as_str())) {
+            Err(format!("Path '{}' should have extension '{}'", val, self.extension().unwrap_or_default()))
+        } else {
+            Ok(())
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct NumberType<T: Clone> {
     value: PossibleArray<T>,
     min_max: Interval<T>,
+    step: Option<T>,
     enum_values: Option<Enum<T>>,
 }
 
@@ -75,6 +300,7 @@ impl <T> NumberType<T> where T: Numeric {
         NumberType {
             value: PossibleArray::Value(T::zero()),
             min_max: Interval::none(),
+            step: None,
             enum_values: None,
         }
     }
@@ -82,6 +308,7 @@ impl <T> NumberType<T> where T: Numeric {
         NumberType {
             value: PossibleArray::Array(Vec::new()),
             min_max: Interval::none(),
+            step: None,
             enum_values: None,
         }
     }
@@ -90,6 +317,7 @@ impl <T> NumberType<T> where T: Numeric {
         NumberType {
             value: PossibleArray::Value(val),
             min_max: Interval::none(),
+            step: None,
             enum_values: None,
         }
     }
@@ -129,6 +357,38 @@ impl <T> NumberType<T> where T: Numeric {
         self.min_max.set_max(val);
     }
 
+    pub fn exclude_min(&mut self) {
+        self.min_max.exclude_min();
+    }
+
+    pub fn exclude_max(&mut self) {
+        self.min_max.exclude_max();
+    }
+
+    pub fn step(&self) -> Option<T> {
+        self.step.clone()
+    }
+
+    pub fn set_step(&mut self, val: T) {
+        self.step = Some(val);
+    }
+
+    pub fn check_step(&self, val: T) -> bool {
+        match &self.step {
+            Some(step) => val.is_multiple_of(step),
+            None => true,
+        }
+    }
+
+    pub fn invalid_enum_value(&self) -> Option<T> {
+        match &self.enum_values {
+            Some(vals) => vals.values().iter()
+                .find(|&v| !self.check_minmax(v.clone()) || !self.check_step(v.clone()))
+                .cloned(),
+            None => None,
+        }
+    }
+
     pub fn enum_values(&self) -> &Option<Enum<T>> {
         &self.enum_values
     }
@@ -144,10 +404,25 @@ impl <T> NumberType<T> where T: Numeric {
         }
     }
 
+    pub fn set_enum_label(&mut self, label: &str) {
+        if let Some(values) = &mut self.enum_values {
+            values.set_last_label(label);
+        }
+    }
+
 }
 
 pub type IntegerType = NumberType<i64>;
-pub type FloatingType = NumberType<f64>;
+pub type UnsignedType = NumberType<u64>;
+pub type BigIntType = NumberType<BigInt>;
+pub type FloatingType = NumberType<Float>;
+pub type DecimalType = NumberType<Decimal>;
+pub type DurationType = NumberType<Duration>;
+pub type SizeType = NumberType<ByteSize>;
+pub type DateType = NumberType<Date>;
+pub type TimeType = NumberType<Time>;
+pub type DateTimeType = NumberType<DateTime>;
+pub type SemverType = NumberType<Version>;
 
 #[derive(Clone)]
 pub struct BooleanType {
@@ -176,7 +451,7 @@ impl BooleanType {
     pub fn get_value(&self) -> bool {
         match &self.value {
             PossibleArray::Value(val) => *val,
-            PossibleArray::Array(arr) => if arr.len() > 0 {
+            PossibleArray::Array(arr) => if !arr.is_empty() {
                 arr[0]
             } else {
                 false
@@ -192,14 +467,16 @@ impl BooleanType {
 #[derive(Clone)]
 pub struct ObjectType {
     value: PossibleArray<Box<Option<ObjectType>>>,
-    fields: HashMap<String, FieldType>,
+    fields: OrderedMap<FieldType>,
+    enum_values: Option<Enum<ObjectType>>,
 }
 
 impl ObjectType {
     pub fn new() -> ObjectType {
         ObjectType {
             value: PossibleArray::Value(Box::new(None)),
-            fields: HashMap::new(),
+            fields: OrderedMap::new(),
+            enum_values: None,
         }
     }
     pub fn has_field(&self, val: &str) -> bool {
@@ -214,7 +491,7 @@ impl ObjectType {
         self.value.add_value(Box::new(Some(value)))
     }
 
-    pub fn fields(&self) -> &HashMap<String, FieldType> {
+    pub fn fields(&self) -> &OrderedMap<FieldType> {
         &self.fields
     }
     
@@ -225,33 +502,94 @@ impl ObjectType {
         self.value = val;
     } 
 
+    // fields of the value if it's defined, otherwise fields with their defaults
+    pub fn value_fields(&self) -> &OrderedMap<FieldType> {
+        match &self.value {
+            PossibleArray::Value(val) => match &**val {
+                Some(unboxed) => unboxed.fields(),
+                None => self.fields(),
+            },
+            PossibleArray::Array(_) => self.fields(),
+        }
+    }
+
     pub fn get_field(&self, key: &str) -> Option<&FieldType> {
         self.fields.get(key)
     }
 
-    pub fn set_fields(&mut self, new_values: HashMap<String, FieldType>) {
+    pub fn set_fields(&mut self, new_values: OrderedMap<FieldType>) {
         self.fields = new_values;
     }
 
-    pub fn clone_fields(&self) -> HashMap<String, FieldType> {
+    pub fn clone_fields(&self) -> OrderedMap<FieldType> {
         self.fields.clone()
     }
+
+    pub fn check_enum(&self, val: &ObjectType) -> bool {
+        match &self.enum_values {
+            Some(vals) => vals.check(val),
+            None => true,
+        }
+    }
+
+    pub fn enum_values(&self) -> &Option<Enum<ObjectType>> {
+        &self.enum_values
+    }
+
+    pub fn add_enum_value(&mut self, val: ObjectType) -> bool {
+        match &mut self.enum_values {
+            Some(values) => values.try_add(val),
+            None => { 
+                self.add_value(val.clone());
+                self.enum_values = Some(Enum::create_with(val));
+                true
+            },
+        }
+    }
+
+    pub fn set_enum_label(&mut self, label: &str) {
+        if let Some(values) = &mut self.enum_values {
+            values.set_last_label(label);
+        }
+    }
+}
+
+// objects are compared by their values, field by field
+impl PartialEq for ObjectType {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.value(), other.value()) {
+            (PossibleArray::Array(a), PossibleArray::Array(b)) => a == b,
+            (PossibleArray::Value(_), PossibleArray::Value(_)) => {
+                let (a, b) = (self.value_fields(), other.value_fields());
+                a.len() == b.len() && a.iter().all(|(k, v)| {
+                    match b.get(k) {
+                        Some(o) => v.value() == o.value(),
+                        None => false,
+                    }
+                })
+            },
+            _ => false,
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct AnyType {
     value: PossibleArray<Box<Option<Element>>>,
+    enum_values: Option<Enum<Element>>,
 }
 
 impl AnyType {
     pub fn new() -> AnyType {
         AnyType {
             value: PossibleArray::Value(Box::new(None)),
+            enum_values: None,
         }
     }
     pub fn new_array() -> AnyType {
         AnyType {
             value: PossibleArray::new_array(),
+            enum_values: None,
         }
     }
     pub fn add_value(&mut self, value: Element) {
@@ -263,6 +601,34 @@ impl AnyType {
     pub fn set_value(&mut self, val: PossibleArray<Box<Option<Element>>>) {
         self.value = val;
     }
+
+    pub fn check_enum(&self, val: &Element) -> bool {
+        match &self.enum_values {
+            Some(vals) => vals.check(val),
+            None => true,
+        }
+    }
+
+    pub fn enum_values(&self) -> &Option<Enum<Element>> {
+        &self.enum_values
+    }
+
+    pub fn add_enum_value(&mut self, val: Element) -> bool {
+        match &mut self.enum_values {
+            Some(values) => values.try_add(val),
+            None => { 
+                self.add_value(val.clone());
+                self.enum_values = Some(Enum::create_with(val));
+                true
+            },
+        }
+    }
+
+    pub fn set_enum_label(&mut self, label: &str) {
+        if let Some(values) = &mut self.enum_values {
+            values.set_last_label(label);
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -270,10 +636,50 @@ pub enum Element {
     None,
     String(StringType),
     Integer(IntegerType),
+    Unsigned(UnsignedType),
+    BigInt(BigIntType),
     Floating(FloatingType),
+    Decimal(DecimalType),
     Boolean(BooleanType),
     Object(ObjectType),
     Any(AnyType),
+    Duration(DurationType),
+    Size(SizeType),
+    Date(DateType),
+    Time(TimeType),
+    DateTime(DateTimeType),
+    Net(NetType),
+    Semver(SemverType),
+    Bytes(BytesType),
+    Path(PathType),
+}
+
+// elements are equal when they have the same type and the same values
+impl PartialEq for Element {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Element::None, Element::None) => true,
+            (Element::String(a), Element::String(b)) => a.value() == b.value(),
+            (Element::Integer(a), Element::Integer(b)) => a.value() == b.value(),
+            (Element::Unsigned(a), Element::Unsigned(b)) => a.value() == b.value(),
+            (Element::BigInt(a), Element::BigInt(b)) => a.value() == b.value(),
+            (Element::Floating(a), Element::Floating(b)) => a.value() == b.value(),
+            (Element::Decimal(a), Element::Decimal(b)) => a.value() == b.value(),
+            (Element::Boolean(a), Element::Boolean(b)) => a.value() == b.value(),
+            (Element::Object(a), Element::Object(b)) => a == b,
+            (Element::Any(a), Element::Any(b)) => a.value() == b.value(),
+            (Element::Duration(a), Element::Duration(b)) => a.value() == b.value(),
+            (Element::Size(a), Element::Size(b)) => a.value() == b.value(),
+            (Element::Date(a), Element::Date(b)) => a.value() == b.value(),
+            (Element::Time(a), Element::Time(b)) => a.value() == b.value(),
+            (Element::DateTime(a), Element::DateTime(b)) => a.value() == b.value(),
+            (Element::Semver(a), Element::Semver(b)) => a.value() == b.value(),
+            (Element::Bytes(a), Element::Bytes(b)) => a.value() == b.value(),
+            (Element::Path(a), Element::Path(b)) => a.value() == b.value(),
+            (Element::Net(a), Element::Net(b)) => a.kind() == b.kind() && a.value() == b.value(),
+            _ => false,
+        }
+    }
 }
 
 impl Element {
@@ -281,7 +687,7 @@ impl Element {
     pub fn as_string(&self) -> Option<&str> {
         match &self {
             Element::String(val) => match val.value().as_value() {
-                Some(v) => Some(&v),
+                Some(v) => Some(v),
                 None => None,
             },
             _ => None
@@ -289,36 +695,21 @@ impl Element {
     }
     pub fn as_integer(&self) -> Option<i64> {
         match self {
-            Element::Integer(val) => match val.value().as_value() {
-                Some(v) => Some(*v),
-                None => None,
-            },
-            Element::Floating(val) => match val.value().as_value() {
-                Some(v) => Some(*v as i64),
-                None => None,
-            },
+            Element::Integer(val) => val.value().as_value().copied(),
+            Element::Floating(val) => val.value().as_value().map(|v| v.value() as i64),
             _ => None
         }
     } 
     pub fn as_floating(&self) -> Option<f64> {
         match self {
-            Element::Integer(val) => match val.value().as_value() {
-                Some(v) => Some(*v as f64),
-                None => None,
-            },
-            Element::Floating(val) => match val.value().as_value() {
-                Some(v) => Some(*v),
-                None => None,
-            },
+            Element::Integer(val) => val.value().as_value().map(|v| *v as f64),
+            Element::Floating(val) => val.value().as_value().map(|v| v.value()),
             _ => None
         }
     } 
     pub fn as_boolean(&self) -> Option<bool> {
         match self {
-            Element::Boolean(val) => match val.value().as_value() {
-                Some(v) => Some(*v),
-                None => None,
-            },
+            Element::Boolean(val) => val.value().as_value().copied(),
             _ => None
         }
     }
@@ -338,23 +729,31 @@ impl Element {
 
 #[derive(Clone)]
 pub struct Options {
-    values: HashMap<String, Element>
+    values: OrderedMap<Element>
 }
 
 impl Options {
     pub fn new() -> Options {
         Options {
-            values: HashMap::new(),
+            values: OrderedMap::new(),
         }
     }
 
     pub fn has_bool(&self, key: &str) -> bool {
         match self.values.get(key) {
-            Some(expr) => match expr {
-                Element::Boolean(opt) => opt.get_value(),
-                _ => false,
-            },
-            None => false,
+            Some(Element::Boolean(opt)) => opt.get_value(),
+            _ => false,
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Element> {
+        self.values.get(key)
+    }
+
+    pub fn get_string(&self, key: &str) -> Option<&str> {
+        match self.values.get(key) {
+            Some(expr) => expr.as_string(),
+            None => None,
         }
     }
 
@@ -364,7 +763,7 @@ impl Options {
     pub fn add(&mut self, key: &str, value: Element) {
         self.values.insert(key.to_string(), value);
     }
-    pub fn all(&self) -> &HashMap<String, Element> {
+    pub fn all(&self) -> &OrderedMap<Element> {
         &self.values
     }
 }
@@ -374,31 +773,89 @@ pub struct FieldType {
     value: Element,
     name: String,
     opts: Options,
+    position: (usize, usize),
+    // the alias the type is from
+    alias: Option<String>,
 }
 
 impl FieldType {
     pub fn new(name: String, value: Element, opts: Options) -> FieldType {
         FieldType{
-            value: value,
-            name: String::from(name),
-            opts: opts,
+            value,
+            name,
+            opts,
+            position: (0, 0),
+            alias: None,
         }
     }
+    // line and column of the declaration, (0, 0) for the fields that are not from a file
+    pub fn position(&self) -> (usize, usize) {
+        self.position
+    }
+    pub fn set_position(&mut self, pos: (usize, usize)) {
+        self.position = pos;
+    }
+    pub fn alias(&self) -> Option<&str> {
+        self.alias.as_deref()
+    }
+    pub fn set_alias(&mut self, name: String) {
+        self.alias = Some(name);
+    }
     pub fn value(&self) -> &Element {
-        return &self.value
+        &self.value
     }
     pub fn name(&self) -> &str {
-        return &self.name
+        &self.name
     }
     pub fn options(&self) -> &Options {
         &self.opts
     }
+    // (deprecated) or (deprecated: "hint")
+    pub fn is_deprecated(&self) -> bool {
+        self.opts.has_bool("deprecated") || self.deprecation_hint().is_some()
+    }
+    pub fn deprecation_hint(&self) -> Option<&str> {
+        self.opts.get_string("deprecated")
+    }
+}
+
+// `profile prod: base { db.pool = 50 }`, the values replace the defaults when the profile is selected
+#[derive(Clone)]
+pub struct Profile {
+    name: String,
+    parent: Option<String>,
+    // paths of the fields with the fields that have the new values
+    values: Vec<(Vec<String>, FieldType)>,
+}
+
+impl Profile {
+    pub fn new(name: String, parent: Option<String>) -> Profile {
+        Profile {
+            name,
+            parent,
+            values: Vec::new(),
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn parent(&self) -> Option<&str> {
+        self.parent.as_deref()
+    }
+    pub fn add_value(&mut self, path: Vec<String>, fld: FieldType) {
+        self.values.push((path, fld));
+    }
+    pub fn values(&self) -> &Vec<(Vec<String>, FieldType)> {
+        &self.values
+    }
 }
 
 #[derive(Clone)]
 pub struct Module {
     name: String,
     fields: Vec<FieldType>,
+    aliases: Vec<FieldType>,
+    profiles: Vec<Profile>,
 }
 
 impl Module {
@@ -407,6 +864,8 @@ impl Module {
         Module {
             name: String::new(),
             fields: Vec::new(),
+            aliases: Vec::new(),
+            profiles: Vec::new(),
         }
     }
     pub fn set_name(&mut self, name: String) {
@@ -428,4 +887,41 @@ impl Module {
     pub fn get_field(&self, name: &str) -> Option<&FieldType> {
         self.fields.iter().find(|v| v.name() == name)
     }
+
+    // the module with one of its fields, the name and the aliases are kept,
+    // profiles keep only the values of the field
+    pub fn only_field(&self, name: &str) -> Option<Module> {
+        let fld = self.get_field(name)?;
+        let profiles = self.profiles.iter().map(|p| Profile {
+            name: p.name.clone(),
+            parent: p.parent.clone(),
+            values: p.values.iter().filter(|(path, _)| path[0] == name).cloned().collect(),
+        }).collect();
+        Some(Module {
+            name: self.name.clone(),
+            fields: vec![fld.clone()],
+            aliases: self.aliases.clone(),
+            profiles,
+        })
+    }
+
+    pub fn add_alias(&mut self, fld: FieldType) {
+        self.aliases.push(fld);
+    }
+
+    pub fn aliases(&self) -> &Vec<FieldType> {
+        &self.aliases
+    }
+
+    pub fn add_profile(&mut self, profile: Profile) {
+        self.profiles.push(profile);
+    }
+
+    pub fn profiles(&self) -> &Vec<Profile> {
+        &self.profiles
+    }
+
+    pub fn get_profile(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|v| v.name() == name)
+    }
 }
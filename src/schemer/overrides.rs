@@ -0,0 +1,256 @@
+use super::objects::*;
+use super::helpers::*;
+use super::json::JsonValue;
+use super::validate::FromJson;
+use super::bigint::BigInt;
+use super::decimal::Float;
+
+// the values of a checked override document replace the defaults,
+// objects are merged field by field, arrays and scalars are replaced
+mod utils {
+    use super::*;
+
+    pub fn replaced<T, F>(current: &PossibleArray<T>, val: &JsonValue, convert: F) -> PossibleArray<T>
+        where T: Clone, F: Fn(&JsonValue) -> Option<T> {
+        match (current.is_array(), val.as_array()) {
+            (true, Some(items)) => PossibleArray::Array(items.iter().filter_map(convert).collect()),
+            (false, _) => match convert(val) {
+                Some(v) => PossibleArray::Value(v),
+                None => current.clone(),
+            },
+            _ => current.clone(),
+        }
+    }
+
+    pub fn number<T: Numeric + FromJson>(num: &NumberType<T>, val: &JsonValue) -> NumberType<T> {
+        let mut res = num.clone();
+        res.set_value(replaced(num.value(), val, T::from_json));
+        res
+    }
+
+    pub fn text(current: &PossibleArray<String>, val: &JsonValue) -> PossibleArray<String> {
+        replaced(current, val, |v| v.as_str().map(|s| s.to_string()))
+    }
+
+    // the same elements the parser guesses for `any`
+    pub fn json_element(val: &JsonValue) -> Element {
+        match val {
+            JsonValue::Null => Element::Any(AnyType::new()),
+            JsonValue::Boolean(v) => Element::Boolean(BooleanType::from(*v)),
+            JsonValue::Number(_) => {
+                if let Some(v) = i64::from_json(val) {
+                    Element::Integer(IntegerType::from(v))
+                } else if let Some(v) = BigInt::from_json(val) {
+                    Element::BigInt(BigIntType::from(v))
+                } else {
+                    Element::Floating(Float::from_json(val).map(FloatingType::from).unwrap_or_else(FloatingType::new))
+                }
+            },
+            JsonValue::String(v) => Element::String(StringType::from(v)),
+            JsonValue::Array(items) => {
+                let mut arr = AnyType::new_array();
+                for item in items {
+                    arr.add_value(json_element(item));
+                }
+                Element::Any(arr)
+            },
+            JsonValue::Object(fields) => {
+                let mut obj = ObjectType::new();
+                for (name, v) in fields.iter() {
+                    obj.add_field(FieldType::new(name.clone(), json_element(v), Options::new()));
+                }
+                Element::Object(obj)
+            },
+        }
+    }
+
+    // objects are merged key by key, other values are replaced
+    pub fn merged_json(current: Option<&Element>, val: &JsonValue) -> Element {
+        match (current, val.as_object()) {
+            (Some(Element::Object(obj)), Some(fields)) => {
+                let mut res = obj.clone();
+                for (name, v) in fields.iter() {
+                    let next = merged_json(obj.get_field(name).map(|f| f.value()), v);
+                    res.add_field(FieldType::new(name.clone(), next, Options::new()));
+                }
+                Element::Object(res)
+            },
+            _ => json_element(val),
+        }
+    }
+
+    // `base` has the current values of the fields
+    pub fn merged_object(obj: &ObjectType, base: &OrderedMap<FieldType>, val: &JsonValue) -> ObjectType {
+        let mut next = ObjectType::new();
+        next.set_fields(base.clone());
+        if let Some(fields) = val.as_object() {
+            for (name, v) in fields.iter() {
+                let schema = match obj.get_field(name) {
+                    Some(fld) => fld,
+                    None => continue,
+                };
+                // values of `any` fields are stored as the elements they were guessed as
+                let current = match schema.value() {
+                    Element::Any(_) => schema,
+                    _ => base.get(name).unwrap_or(schema),
+                };
+                next.add_field(merged_field(current, v));
+            }
+        }
+        next
+    }
+
+    pub fn object(obj: &ObjectType, val: &JsonValue) -> ObjectType {
+        let mut res = obj.clone();
+        match (obj.value().is_array(), val.as_array()) {
+            (true, Some(items)) => {
+                let values = items.iter().map(|item| Box::new(Some(merged_object(obj, obj.fields(), item)))).collect();
+                res.set_value(PossibleArray::Array(values));
+            },
+            (false, _) => {
+                res.set_value(PossibleArray::Value(Box::new(Some(merged_object(obj, obj.value_fields(), val)))));
+            },
+            _ => {},
+        }
+        res
+    }
+
+    pub fn merged_element(current: &Element, val: &JsonValue) -> Element {
+        match current {
+            Element::None => Element::None,
+            Element::Boolean(v) => {
+                let mut res = v.clone();
+                res.set_value(replaced(v.value(), val, |item| item.as_bool()));
+                Element::Boolean(res)
+            },
+            Element::String(v) => {
+                let mut res = v.clone();
+                res.set_value(text(v.value(), val));
+                Element::String(res)
+            },
+            Element::Net(v) => {
+                let mut res = v.clone();
+                res.set_value(replaced(v.value(), val, |item| v.kind().normalize(item.as_str()?).ok()));
+                Element::Net(res)
+            },
+            Element::Bytes(v) => {
+                let mut res = v.clone();
+                res.set_value(text(v.value(), val));
+                Element::Bytes(res)
+            },
+            Element::Path(v) => {
+                let mut res = v.clone();
+                res.set_value(text(v.value(), val));
+                Element::Path(res)
+            },
+            Element::Integer(v) => Element::Integer(number(v, val)),
+            Element::Unsigned(v) => Element::Unsigned(number(v, val)),
+            Element::BigInt(v) => Element::BigInt(number(v, val)),
+            Element::Floating(v) => Element::Floating(number(v, val)),
+            Element::Decimal(v) => Element::Decimal(number(v, val)),
+            Element::Duration(v) => Element::Duration(number(v, val)),
+            Element::Size(v) => Element::Size(number(v, val)),
+            Element::Date(v) => Element::Date(number(v, val)),
+            Element::Time(v) => Element::Time(number(v, val)),
+            Element::DateTime(v) => Element::DateTime(number(v, val)),
+            Element::Semver(v) => Element::Semver(number(v, val)),
+            Element::Object(v) => Element::Object(object(v, val)),
+            Element::Any(v) => {
+                let mut res = v.clone();
+                let current = v.value().as_value().and_then(|item| item.as_ref().as_ref());
+                res.set_value(replaced(v.value(), val, |item| Some(Box::new(Some(merged_json(current, item))))));
+                Element::Any(res)
+            },
+        }
+    }
+
+    // the field with `value` at `path` inside it
+    pub fn with_value(fld: &FieldType, path: &[String], value: &FieldType) -> FieldType {
+        let (name, rest) = match path.split_first() {
+            Some(v) => v,
+            None => return value.clone(),
+        };
+        let obj = match fld.value() {
+            Element::Object(obj) => obj,
+            _ => return fld.clone(),
+        };
+        let base = obj.value_fields();
+        let mut next = ObjectType::new();
+        next.set_fields(base.clone());
+        if let Some(current) = base.get(name).or_else(|| obj.get_field(name)) {
+            next.add_field(with_value(current, rest, value));
+        }
+        let mut res = obj.clone();
+        res.set_value(PossibleArray::Value(Box::new(Some(next))));
+        let mut out = FieldType::new(fld.name().to_string(), Element::Object(res), fld.options().clone());
+        out.set_position(fld.position());
+        out
+    }
+
+    pub fn merged_field(fld: &FieldType, val: &JsonValue) -> FieldType {
+        let mut res = FieldType::new(fld.name().to_string(), merged_element(fld.value(), val), fld.options().clone());
+        res.set_position(fld.position());
+        res
+    }
+}
+
+// the module with the values of the document, it should be checked with `validate_overrides` first
+pub fn apply_overrides(module: &Module, val: &JsonValue) -> Module {
+    let mut res = Module::new();
+    res.set_name(module.name().to_string());
+    for alias in module.aliases() {
+        res.add_alias(alias.clone());
+    }
+    for profile in module.profiles() {
+        res.add_profile(profile.clone());
+    }
+    for fld in module.fields() {
+        match val.get(fld.name()) {
+            Some(v) => res.add_field(utils::merged_field(fld, v)),
+            None => res.add_field(fld.clone()),
+        }
+    }
+    res
+}
+
+// the element with a value from json, the value should be checked with `validate_field` first
+pub fn element_with_value(element: &Element, val: &JsonValue) -> Element {
+    utils::merged_element(element, val)
+}
+
+// the module with the values of the profile, the values of its parents are set first
+pub fn apply_profile(module: &Module, name: &str) -> Result<Module, String> {
+    let mut chain = Vec::new();
+    let mut next = Some(name);
+    while let Some(current) = next {
+        let profile = match module.get_profile(current) {
+            Some(v) => v,
+            None => {
+                let names = module.profiles().iter().map(|p| p.name()).collect::<Vec<&str>>();
+                return Err(format!("Profile '{}' is not found. Profiles: {}", current, names.join(", ")));
+            },
+        };
+        chain.push(profile);
+        next = profile.parent();
+    }
+    let mut fields = module.fields().clone();
+    for profile in chain.iter().rev() {
+        for (path, value) in profile.values() {
+            if let Some(fld) = fields.iter_mut().find(|f| f.name() == path[0]) {
+                *fld = utils::with_value(fld, &path[1..], value);
+            }
+        }
+    }
+    let mut res = Module::new();
+    res.set_name(module.name().to_string());
+    for alias in module.aliases() {
+        res.add_alias(alias.clone());
+    }
+    for profile in module.profiles() {
+        res.add_profile(profile.clone());
+    }
+    for fld in fields {
+        res.add_field(fld);
+    }
+    Ok(res)
+}
@@ -1,8 +1,16 @@
 
+use std::convert::TryFrom;
 use super::tokens::{TokenInfo, Token, SpecialToken, TypeName};
 use super::objects::*;
 use super::object_base::*;
 use super::environment::{Environment};
+use super::helpers::{Numeric, UnitValue, Duration, ByteSize, PossibleArray};
+use super::datetime::{Date, Time, DateTime};
+use super::network::{NetKind, Network};
+use super::semver::Version;
+use super::encoding::Encoding;
+use super::bigint::BigInt;
+use super::decimal::{Float, Decimal};
 
 struct ParserState {
     current: usize,
@@ -31,6 +39,8 @@ pub struct Parser {
     next: usize,
     eof_token: TokenInfo, 
     env: Environment,
+    warnings: Vec<String>,
+    check_paths: bool,
 }
 
 mod helpers {
@@ -48,79 +58,461 @@ mod helpers {
         }
     }
 
+    // integers that don't fit i64 are an error for `integer`, `bigint` keeps them
+    fn read_integer(parser: &mut Parser) -> Result<Option<i64>, ParserError> {
+        let sign = read_sign(parser);
+        let val = match parser.current().token() {
+            Token::Integer(val) => BigInt::from(*val),
+            Token::BigInteger(val) => val.clone(),
+            _ => return Ok(None),
+        };
+        let val = if sign < 0 { val.negate() } else { val };
+        match val.to_i64() {
+            Some(v) => Ok(Some(v)),
+            None => Err(parser.panic_current(&format!("Value {} is out of range for integer", val))),
+        }
+    }
+
+    // integers keep their spelling too
+    fn read_floating(parser: &mut Parser) -> Option<Float> {
+        let sign = read_sign(parser);
+        let val = match parser.current().token() {
+            Token::Floating(val) => Some(val.clone()),
+            Token::Integer(val) => Float::parse(&val.to_string()).ok(),
+            Token::BigInteger(val) => Float::parse(&val.to_string()).ok(),
+            _ => None,
+        };
+        val.map(|v| if sign < 0 { v.negate() } else { v })
+    }
+
+    fn is_signed_value_start<T: FromToken>(tok: &Token) -> bool {
+        T::from_token(tok).is_some()
+            || Token::is_special(SpecialToken::Minus)(tok)
+            || Token::is_special(SpecialToken::Plus)(tok)
+    }
+
     pub trait WithInterval {
-        fn set_min(&mut self, parser: &mut Parser);
-        fn set_max(&mut self, parser: &mut Parser);
+        fn set_min(&mut self, parser: &mut Parser) -> Result<(), ParserError>;
+        fn set_max(&mut self, parser: &mut Parser) -> Result<(), ParserError>;
+        fn exclude_min(&mut self);
+        fn exclude_max(&mut self);
     }
     
     impl WithInterval for IntegerType {
-        fn set_min(&mut self, parser: &mut Parser) {
-            let sign = read_sign(parser) as i64;
-            match &parser.current().token() {
-                Token::Integer(val) => self.set_min(*val * sign),
-                _ => (),
+        fn set_min(&mut self, parser: &mut Parser) -> Result<(), ParserError> {
+            if let Some(val) = read_integer(parser)? {
+                self.set_min(val);
             }
+            Ok(())
         }
-        fn set_max(&mut self, parser: &mut Parser) {
-            let sign = read_sign(parser) as i64;
-            match &parser.current().token() {
-                Token::Integer(val) => {
-                    self.set_max(*val * sign)
-                },
-                _ => (),
+        fn set_max(&mut self, parser: &mut Parser) -> Result<(), ParserError> {
+            if let Some(val) = read_integer(parser)? {
+                self.set_max(val);
             }
+            Ok(())
+        }
+        fn exclude_min(&mut self) {
+            self.exclude_min();
+        }
+        fn exclude_max(&mut self) {
+            self.exclude_max();
         }
     }
 
     impl WithInterval for FloatingType {
-        fn set_min(&mut self, parser: &mut Parser) {
-            let sign = read_sign(parser) as f64;
-            match parser.current().token() {
-                Token::Floating(val) => self.set_min(*val * sign),
-                Token::Integer(val) => self.set_min(*val as f64 * sign),
-                _ => (),
+        fn set_min(&mut self, parser: &mut Parser) -> Result<(), ParserError> {
+            if let Some(val) = read_floating(parser) {
+                self.set_min(val);
+            }
+            Ok(())
+        }
+        fn set_max(&mut self, parser: &mut Parser) -> Result<(), ParserError> {
+            if let Some(val) = read_floating(parser) {
+                self.set_max(val);
+            }
+            Ok(())
+        }
+        fn exclude_min(&mut self) {
+            self.exclude_min();
+        }
+        fn exclude_max(&mut self) {
+            self.exclude_max();
+        }
+    }
+
+    // value types of the tokens: durations, sizes, dates, times, versions and big integers
+    pub trait FromToken: Sized {
+        fn from_token(tok: &Token) -> Option<Self>;
+        // signed values also start with + or -
+        fn is_value_start(tok: &Token) -> bool {
+            Self::from_token(tok).is_some()
+        }
+        fn read_token(parser: &mut Parser) -> Option<Self> {
+            Self::from_token(parser.current().token())
+        }
+        // why a token that starts a value can't be read
+        fn token_error(_tok: &Token) -> Option<String> {
+            None
+        }
+    }
+
+    fn invalid_value<T: Numeric + FromToken>(parser: &mut Parser) -> ParserError {
+        let msg = T::token_error(parser.current().token()).unwrap_or_else(|| format!("Value is out of range for {}", T::name()));
+        parser.panic_current(&msg)
+    }
+
+    impl FromToken for u64 {
+        fn from_token(tok: &Token) -> Option<Self> {
+            match tok {
+                Token::Integer(val) => u64::try_from(*val).ok(),
+                Token::BigInteger(val) => val.to_u64(),
+                _ => None,
+            }
+        }
+        // big integers are values too, so they are reported as out of range
+        fn is_value_start(tok: &Token) -> bool {
+            matches!(tok, Token::Integer(_) | Token::BigInteger(_))
+        }
+    }
+
+    impl FromToken for BigInt {
+        fn from_token(tok: &Token) -> Option<Self> {
+            match tok {
+                Token::Integer(val) => Some(BigInt::from(*val)),
+                Token::BigInteger(val) => Some(val.clone()),
+                _ => None,
+            }
+        }
+        fn is_value_start(tok: &Token) -> bool {
+            is_signed_value_start::<BigInt>(tok)
+        }
+        fn read_token(parser: &mut Parser) -> Option<Self> {
+            let sign = read_sign(parser);
+            BigInt::from_token(parser.current().token()).map(|v| if sign < 0 { v.negate() } else { v })
+        }
+    }
+
+    // decimals are read from the literal, so 0.1 is exact. Strings are allowed like for dates
+    impl FromToken for Decimal {
+        fn from_token(tok: &Token) -> Option<Self> {
+            match tok {
+                Token::Floating(val) => Decimal::parse(&val.to_string().replace('_', "")).ok(),
+                Token::Integer(val) => Decimal::parse(&val.to_string()).ok(),
+                Token::BigInteger(val) => Decimal::parse(&val.to_string()).ok(),
+                Token::String(val) => Decimal::parse(val).ok(),
+                _ => None,
+            }
+        }
+        fn is_value_start(tok: &Token) -> bool {
+            is_signed_value_start::<Decimal>(tok)
+        }
+        fn read_token(parser: &mut Parser) -> Option<Self> {
+            let sign = read_sign(parser);
+            Decimal::from_token(parser.current().token()).map(|v| if sign < 0 { v.negate() } else { v })
+        }
+    }
+
+    impl FromToken for Duration {
+        fn from_token(tok: &Token) -> Option<Self> {
+            match tok {
+                Token::Duration(val) => Some(Duration::from_millis(*val)),
+                _ => None,
+            }
+        }
+    }
+
+    impl FromToken for ByteSize {
+        fn from_token(tok: &Token) -> Option<Self> {
+            match tok {
+                Token::Size(val, unit) => Some(ByteSize::with_unit(*val, *unit)),
+                _ => None,
+            }
+        }
+    }
+
+    // dates and times can also be written as strings, like in JSON
+    impl FromToken for Date {
+        fn from_token(tok: &Token) -> Option<Self> {
+            match tok {
+                Token::Date(val) => Some(*val),
+                Token::String(val) => Date::parse(val).ok(),
+                _ => None,
+            }
+        }
+        // a quoted value is read as this type, so the error tells what is wrong with it
+        fn is_value_start(tok: &Token) -> bool {
+            tok.is_string() || Self::from_token(tok).is_some()
+        }
+        fn token_error(tok: &Token) -> Option<String> {
+            match tok {
+                Token::String(val) => Date::parse(val).err(),
+                _ => None,
+            }
+        }
+    }
+
+    impl FromToken for Time {
+        fn from_token(tok: &Token) -> Option<Self> {
+            match tok {
+                Token::Time(val) => Some(*val),
+                Token::String(val) => Time::parse(val).ok(),
+                _ => None,
+            }
+        }
+        fn is_value_start(tok: &Token) -> bool {
+            tok.is_string() || Self::from_token(tok).is_some()
+        }
+        fn token_error(tok: &Token) -> Option<String> {
+            match tok {
+                Token::String(val) => Time::parse(val).err(),
+                _ => None,
+            }
+        }
+    }
+
+    impl FromToken for DateTime {
+        fn from_token(tok: &Token) -> Option<Self> {
+            match tok {
+                Token::DateTime(val) => Some(*val),
+                Token::Date(val) => Some(DateTime::from_date(*val)),
+                Token::String(val) => DateTime::parse(val).ok(),
+                _ => None,
+            }
+        }
+        fn is_value_start(tok: &Token) -> bool {
+            tok.is_string() || Self::from_token(tok).is_some()
+        }
+        fn token_error(tok: &Token) -> Option<String> {
+            match tok {
+                Token::String(val) => DateTime::parse(val).err(),
+                _ => None,
+            }
+        }
+    }
+
+    impl FromToken for Version {
+        fn from_token(tok: &Token) -> Option<Self> {
+            match tok {
+                Token::Version(val) => Some(val.clone()),
+                Token::String(val) => Version::parse(val).ok(),
+                _ => None,
+            }
+        }
+        fn is_value_start(tok: &Token) -> bool {
+            tok.is_string() || Self::from_token(tok).is_some()
+        }
+        fn token_error(tok: &Token) -> Option<String> {
+            match tok {
+                Token::String(val) => Version::parse(val).err(),
+                _ => None,
+            }
+        }
+    }
+
+    // a bound of the interval that doesn't fit the type is an error, like a default
+    impl<T: Numeric + FromToken> WithInterval for NumberType<T> {
+        fn set_min(&mut self, parser: &mut Parser) -> Result<(), ParserError> {
+            match T::read_token(parser) {
+                Some(val) => {
+                    self.set_min(val);
+                    Ok(())
+                },
+                None => Err(invalid_value::<T>(parser)),
+            }
+        }
+        fn set_max(&mut self, parser: &mut Parser) -> Result<(), ParserError> {
+            match T::read_token(parser) {
+                Some(val) => {
+                    self.set_max(val);
+                    Ok(())
+                },
+                None => Err(invalid_value::<T>(parser)),
+            }
+        }
+        fn exclude_min(&mut self) {
+            self.exclude_min();
+        }
+        fn exclude_max(&mut self) {
+            self.exclude_max();
+        }
+    }
+
+    // only values with units have a step, dates and times don't
+    impl<T: Numeric + FromToken + UnitValue> WithStep for NumberType<T> {
+        fn set_step(&mut self, parser: &mut Parser) -> Result<(), ParserError> {
+            match T::read_token(parser) {
+                Some(val) if val > T::zero() => {
+                    self.set_step(val);
+                    Ok(())
+                },
+                _ => Err(parser.panic_current(&format!("Step should be a positive {}", T::name()))),
+            }
+        }
+    }
+
+    impl WithStep for DecimalType {
+        fn set_step(&mut self, parser: &mut Parser) -> Result<(), ParserError> {
+            match Decimal::read_token(parser) {
+                Some(val) if val > Decimal::zero() => {
+                    self.set_step(val);
+                    Ok(())
+                },
+                _ => Err(parser.panic_current("Step should be a positive decimal")),
+            }
+        }
+    }
+
+    impl WithStep for UnsignedType {
+        fn set_step(&mut self, parser: &mut Parser) -> Result<(), ParserError> {
+            match u64::read_token(parser) {
+                Some(val) if val > 0 => {
+                    self.set_step(val);
+                    Ok(())
+                },
+                _ => Err(parser.panic_current("Step should be a positive unsigned")),
+            }
+        }
+    }
+
+    impl<T: Numeric + FromToken> WithEnum for NumberType<T> {
+        fn enum_add_value(&mut self, parser: &mut Parser) -> Result<bool, ParserError> {
+            match T::read_token(parser) {
+                Some(val) => Ok(self.add_enum_value(val)),
+                None => Err(invalid_value::<T>(parser)),
+            }
+        }
+        fn enum_set_label(&mut self, label: &str) {
+            self.set_enum_label(label);
+        }
+    }
+
+    impl<T: Numeric + FromToken> ValueReadCheck for NumberType<T> {
+        fn token_checker(val: &Token) -> bool {
+            T::is_value_start(val)
+        }
+        fn expected() -> &'static str {
+            T::name()
+        }
+        fn read_value(&mut self, parser: &mut Parser) -> Result<(), ParserError> {
+            let val = match T::read_token(parser) {
+                Some(val) => val,
+                None => return Err(invalid_value::<T>(parser)),
+            };
+            if !self.check_enum(val.clone()) {
+                Err(parser.panic_current(&format!("Value {} is invalid for {} enum", val.to_string(), T::name())))
+            } else if !self.check_minmax(val.clone()) {
+                Err(parser.panic_current(&format!("Value {} is invalid for {} interval", val.to_string(), T::name())))
+            } else if !self.check_step(val.clone()) {
+                Err(parser.panic_current(&format!("Value {} is not a multiple of {} step", val.to_string(), T::name())))
+            } else {
+                self.add_value(val);
+                Ok(())
             }
         }
-        fn set_max(&mut self, parser: &mut Parser) {
-            let sign = read_sign(parser) as f64;
+    }
+
+    pub trait WithStep {
+        fn set_step(&mut self, parser: &mut Parser) -> Result<(), ParserError>;
+    }
+
+    impl WithStep for IntegerType {
+        fn set_step(&mut self, parser: &mut Parser) -> Result<(), ParserError> {
             match parser.current().token() {
-                Token::Floating(val) => self.set_max(*val * sign),
-                Token::Integer(val) => self.set_max(*val as f64 * sign),
-                _ => (),
+                Token::Integer(val) if *val > 0 => {
+                    self.set_step(*val);
+                    Ok(())
+                },
+                Token::BigInteger(val) => Err(parser.panic_current(&format!("Value {} is out of range for integer", val))),
+                _ => Err(parser.panic_current("Step should be a positive integer")),
+            }
+        }
+    }
+
+    impl WithStep for FloatingType {
+        fn set_step(&mut self, parser: &mut Parser) -> Result<(), ParserError> {
+            match read_floating(parser) {
+                Some(val) if val > Float::zero() => {
+                    self.set_step(val);
+                    Ok(())
+                },
+                _ => Err(parser.panic_current("Step should be a positive number")),
             }
         }
     }
 
     pub trait WithEnum {
-        fn enum_add_value(&mut self, parser: &mut Parser) -> bool;
+        fn enum_add_value(&mut self, parser: &mut Parser) -> Result<bool, ParserError>;
+        fn enum_set_label(&mut self, label: &str);
     }
     
     impl WithEnum for IntegerType {
-        fn enum_add_value(&mut self, parser: &mut Parser) -> bool {
-            match &parser.current().token() {
-                Token::Integer(val) => self.add_enum_value(*val),
-                _ => true,
+        fn enum_add_value(&mut self, parser: &mut Parser) -> Result<bool, ParserError> {
+            match read_integer(parser)? {
+                Some(val) => Ok(self.add_enum_value(val)),
+                None => Ok(true),
             }
         }
+        fn enum_set_label(&mut self, label: &str) {
+            self.set_enum_label(label);
+        }
     }
 
     impl WithEnum for FloatingType {
-        fn enum_add_value(&mut self, parser: &mut Parser) -> bool {
-            match &parser.current().token() {
-                Token::Integer(val) => self.add_enum_value(*val as f64),
-                Token::Floating(val) => self.add_enum_value(*val),
-                _ => true,
+        fn enum_add_value(&mut self, parser: &mut Parser) -> Result<bool, ParserError> {
+            match read_floating(parser) {
+                Some(val) => Ok(self.add_enum_value(val)),
+                None => Ok(true),
             }
         }
+        fn enum_set_label(&mut self, label: &str) {
+            self.set_enum_label(label);
+        }
     }
 
     impl WithEnum for StringType {
-        fn enum_add_value(&mut self, parser: &mut Parser) -> bool {
+        fn enum_add_value(&mut self, parser: &mut Parser) -> Result<bool, ParserError> {
             match &parser.current().token() {
-                Token::String(val) => self.add_enum_value(val),
-                _ => true,
+                Token::String(val) => Ok(self.add_enum_value(val)),
+                _ => Ok(true),
             }
         }
+        fn enum_set_label(&mut self, label: &str) {
+            self.set_enum_label(label);
+        }
+    }
+
+    impl WithEnum for NetType {
+        fn enum_add_value(&mut self, parser: &mut Parser) -> Result<bool, ParserError> {
+            match &parser.current().token() {
+                Token::String(val) => match self.kind().normalize(val) {
+                    Ok(v) => Ok(self.add_enum_value(&v)),
+                    Err(err) => Err(parser.panic_current(&err)),
+                },
+                _ => Ok(true),
+            }
+        }
+        fn enum_set_label(&mut self, label: &str) {
+            self.set_enum_label(label);
+        }
+    }
+
+    impl WithEnum for ObjectType {
+        fn enum_add_value(&mut self, parser: &mut Parser) -> Result<bool, ParserError> {
+            // the value is read by an object with the same fields but without the enum
+            let mut tmp = ObjectType::new();
+            tmp.set_fields(self.clone_fields());
+            tmp.read_value(parser)?;
+            match tmp.value().as_value() {
+                Some(val) => match &**val {
+                    Some(unboxed) => Ok(self.add_enum_value(unboxed.clone())),
+                    None => Ok(true),
+                },
+                None => Ok(true),
+            }
+        }
+        fn enum_set_label(&mut self, label: &str) {
+            self.set_enum_label(label);
+        }
     }
 
     pub trait ValueReadCheck {
@@ -144,7 +536,91 @@ mod helpers {
                     if !self.check_enum(val) {
                         return Err(parser.panic_current(&format!("Value '{}' is invalid for enum.", val)));
                     }
-                    Ok(self.add_value(val))
+                    self.add_value(val);
+                    Ok(())
+                },
+                _ => Ok(())
+            }
+        }
+    }
+
+    // addresses are strings, the kind of the address is checked by the value
+    impl ValueReadCheck for NetType {
+        fn token_checker(val: &Token) -> bool {
+            val.is_string()
+        }
+
+        fn expected() -> &'static str {
+            "address string"
+        }
+
+        fn read_value(&mut self, parser: &mut Parser) -> Result<(), ParserError> {
+            match parser.current().token() {
+                Token::String(val) => {
+                    let val = match self.kind().normalize(val) {
+                        Ok(v) => v,
+                        Err(err) => return Err(parser.panic_current(&err)),
+                    };
+                    if !self.check_enum(&val) {
+                        return Err(parser.panic_current(&format!("Value '{}' is invalid for enum.", val)));
+                    } else if !self.check_networks(&val) {
+                        return Err(parser.panic_current(&format!("Value '{}' is not within the networks", val)));
+                    }
+                    self.add_value(&val);
+                    Ok(())
+                },
+                _ => Ok(())
+            }
+        }
+    }
+
+    impl ValueReadCheck for BytesType {
+        fn token_checker(val: &Token) -> bool {
+            val.is_string()
+        }
+
+        fn expected() -> &'static str {
+            "encoded string"
+        }
+
+        fn read_value(&mut self, parser: &mut Parser) -> Result<(), ParserError> {
+            match parser.current().token() {
+                Token::String(val) => {
+                    let len = match self.encoding().decode(val) {
+                        Some(data) => data.len(),
+                        None => return Err(parser.panic_current(&format!("Value is not valid {}", self.encoding().name()))),
+                    };
+                    if !self.check_length(len) {
+                        return Err(parser.panic_current(&format!("Length {} of the decoded value is invalid for bytes interval", len)));
+                    }
+                    self.add_value(val);
+                    Ok(())
+                },
+                _ => Ok(())
+            }
+        }
+    }
+
+    impl ValueReadCheck for PathType {
+        fn token_checker(val: &Token) -> bool {
+            val.is_string()
+        }
+
+        fn expected() -> &'static str {
+            "path string"
+        }
+
+        fn read_value(&mut self, parser: &mut Parser) -> Result<(), ParserError> {
+            match parser.current().token() {
+                Token::String(val) => {
+                    if let Err(err) = self.check(val) {
+                        return Err(parser.panic_current(&err));
+                    }
+                    if self.must_exist() && parser.check_paths() && !std::path::Path::new(val).exists() {
+                        return Err(parser.panic_current(&format!("Path '{}' does not exist", val)));
+                    }
+                    self.add_value(val);
+                    Ok(())
                 },
                 _ => Ok(())
             }
@@ -154,12 +630,9 @@ mod helpers {
     impl ValueReadCheck for IntegerType {
         fn token_checker(val: &Token) -> bool {
             match val {
-                Token::Integer(_) => true,
-                Token::Special(v) => match v {
-                    SpecialToken::Plus => true,
-                    SpecialToken::Minus => true,
-                    _ => false,
-                },
+                Token::Integer(_)
+                | Token::BigInteger(_) => true,
+                Token::Special(v) => matches!(v, SpecialToken::Plus | SpecialToken::Minus),
                 _ => false
             }
         }
@@ -169,19 +642,20 @@ mod helpers {
         }
 
         fn read_value(&mut self, parser: &mut Parser) -> Result<(), ParserError> {
-            let sign = read_sign(parser) as i64;
-            match parser.current().token() {
-                Token::Integer(val) => {
-                    let result = *val * sign; 
+            match read_integer(parser)? {
+                Some(result) => {
                     if !self.check_enum(result) {
-                        return Err(parser.panic_current(&format!("Value {} is invalid for integer enum", result)));
+                        Err(parser.panic_current(&format!("Value {} is invalid for integer enum", result)))
                     } else if !self.check_minmax(result) {
-                        return Err(parser.panic_current(&format!("Value {} is invalid for integer interval", result)));
+                        Err(parser.panic_current(&format!("Value {} is invalid for integer interval", result)))
+                    } else if !self.check_step(result) {
+                        Err(parser.panic_current(&format!("Value {} is not a multiple of integer step", result)))
                     } else {
-                        Ok(self.add_value(result))
+                        self.add_value(result);
+                        Ok(())
                     }
                 },
-                _ => Ok(())
+                None => Ok(())
             }
         }
     }
@@ -190,12 +664,9 @@ mod helpers {
         fn token_checker(val: &Token) -> bool {
             match val {
                 Token::Integer(_) 
+                | Token::BigInteger(_)
                 | Token::Floating(_) => true,
-                Token::Special(v) => match v {
-                    SpecialToken::Plus 
-                    | SpecialToken::Minus => true,
-                    _ => false,
-                },
+                Token::Special(v) => matches!(v, SpecialToken::Plus | SpecialToken::Minus),
                 _ => false
             }
         }
@@ -203,19 +674,20 @@ mod helpers {
             "floating or integer"
         }
         fn read_value(&mut self, parser: &mut Parser) -> Result<(), ParserError> {
-            let sign = read_sign(parser) as f64;
-            let val = match parser.current().token() {
-                Token::Floating(val) => *val,
-                Token::Integer(val) => *val as f64,
-                _ => return Err(parser.panic_current("Should not be here"))
-            } * sign;
-
-            if !self.check_enum(val) {
-                return Err(parser.panic_current(&format!("Value {} is invalid for floating enum", val)));
-            } else if !self.check_minmax(val) {
-                return Err(parser.panic_current(&format!("Value {} is invalid for floating interval", val)));
+            let val = match read_floating(parser) {
+                Some(val) => val,
+                None => return Err(parser.panic_current("Should not be here"))
+            };
+
+            if !self.check_enum(val.clone()) {
+                Err(parser.panic_current(&format!("Value {} is invalid for floating enum", val)))
+            } else if !self.check_minmax(val.clone()) {
+                Err(parser.panic_current(&format!("Value {} is invalid for floating interval", val)))
+            } else if !self.check_step(val.clone()) {
+                Err(parser.panic_current(&format!("Value {} is not a multiple of floating step", val)))
             } else {
-                Ok(self.add_value(val))
+                self.add_value(val);
+                Ok(())
             }
         }
     }
@@ -230,19 +702,21 @@ mod helpers {
         fn read_value(&mut self, parser: &mut Parser) -> Result<(), ParserError> {
             match parser.current().token() {
                 Token::Boolean(val) => {
-                    Ok(self.add_value(*val))
+                    self.add_value(*val);
+                    Ok(())
                 },
                 _ => Ok(())
             }
         }
     }
 
-    fn create_same_object<T: ObjectBase>(val: &T) -> T {
-        let mut res = T::create();
+    // keeps enums and intervals of the original, so the values read are checked against them
+    pub fn create_same_object<T: ObjectBase + Clone>(val: &T) -> T {
+        let mut res = val.clone();
         if val.is_array() {
             res.make_array();
         }
-        return res;
+        res
     }
 
     impl ValueReadCheck for ObjectType {
@@ -268,7 +742,13 @@ mod helpers {
                 let field = self.get_field(&field_name);
                 match field {
                     Some(value) => {
-                        let opts = Options::new(); //value.options().clone();
+                        if value.is_deprecated() {
+                            parser.warn_current(&match value.deprecation_hint() {
+                                Some(hint) => format!("Field '{}' is deprecated: {}", field_name, hint),
+                                None => format!("Field '{}' is deprecated", field_name),
+                            });
+                        }
+                        let opts = value.options().clone();
                         match value.value() {
                             Element::None => (),
                             Element::String(v) => { 
@@ -281,11 +761,71 @@ mod helpers {
                                 parser.read_value(&mut val)?;
                                 next.add_field(FieldType::new(field_name, Element::Integer(val), opts));
                             },
+                            Element::Unsigned(v) => {
+                                let mut val = create_same_object(v);
+                                parser.read_value(&mut val)?;
+                                next.add_field(FieldType::new(field_name, Element::Unsigned(val), opts));
+                            },
+                            Element::BigInt(v) => {
+                                let mut val = create_same_object(v);
+                                parser.read_value(&mut val)?;
+                                next.add_field(FieldType::new(field_name, Element::BigInt(val), opts));
+                            },
                             Element::Floating(v) => {
                                 let mut val = create_same_object(v);
                                 parser.read_value(&mut val)?;
                                 next.add_field(FieldType::new(field_name, Element::Floating(val), opts));
                             },
+                            Element::Decimal(v) => {
+                                let mut val = create_same_object(v);
+                                parser.read_value(&mut val)?;
+                                next.add_field(FieldType::new(field_name, Element::Decimal(val), opts));
+                            },
+                            Element::Duration(v) => {
+                                let mut val = create_same_object(v);
+                                parser.read_value(&mut val)?;
+                                next.add_field(FieldType::new(field_name, Element::Duration(val), opts));
+                            },
+                            Element::Size(v) => {
+                                let mut val = create_same_object(v);
+                                parser.read_value(&mut val)?;
+                                next.add_field(FieldType::new(field_name, Element::Size(val), opts));
+                            },
+                            Element::Path(v) => {
+                                let mut val = create_same_object(v);
+                                parser.read_value(&mut val)?;
+                                next.add_field(FieldType::new(field_name, Element::Path(val), opts));
+                            },
+                            Element::Bytes(v) => {
+                                let mut val = create_same_object(v);
+                                parser.read_value(&mut val)?;
+                                next.add_field(FieldType::new(field_name, Element::Bytes(val), opts));
+                            },
+                            Element::Semver(v) => {
+                                let mut val = create_same_object(v);
+                                parser.read_value(&mut val)?;
+                                next.add_field(FieldType::new(field_name, Element::Semver(val), opts));
+                            },
+                            Element::Net(v) => {
+                                let mut val = create_same_object(v);
+                                parser.read_value(&mut val)?;
+                                next.add_field(FieldType::new(field_name, Element::Net(val), opts));
+                            },
+                            Element::Date(v) => {
+                                let mut val = create_same_object(v);
+                                parser.read_value(&mut val)?;
+                                next.add_field(FieldType::new(field_name, Element::Date(val), opts));
+                            },
+                            Element::Time(v) => {
+                                let mut val = create_same_object(v);
+                                parser.read_value(&mut val)?;
+                                next.add_field(FieldType::new(field_name, Element::Time(val), opts));
+                            },
+                            Element::DateTime(v) => {
+                                let mut val = create_same_object(v);
+                                parser.read_value(&mut val)?;
+                                next.add_field(FieldType::new(field_name, Element::DateTime(val), opts));
+                            },
                             Element::Boolean(v) => { 
                                 let mut val = create_same_object(v);
                                 parser.read_value(&mut val)?;
@@ -293,14 +833,16 @@ mod helpers {
                             },
                             Element::Object(v) => { 
                                 let mut val = create_same_object(v);
-                                val.set_fields(v.clone_fields());
                                 parser.read_value(&mut val)?;
                                 next.add_field(FieldType::new(field_name, Element::Object(val), opts));
                             },
-                            Element::Any(_) => { 
+                            Element::Any(v) => { 
                                 if parser.expect(&Token::is_special(SpecialToken::Equal)) || 
                                 parser.expect(&Token::is_special(SpecialToken::Colon)) {
                                     let val = parser.guess_element()?;
+                                    if !v.check_enum(&val) {
+                                        return Err(parser.panic_current(&format!("Value of '{}' is invalid for any enum", field_name)));
+                                    }
                                     next.add_field(FieldType::new(field_name, val, opts));
                                 }
                             },
@@ -317,6 +859,9 @@ mod helpers {
                 }
                 parser.expect(&Token::is_special(SpecialToken::Comma));
             }
+            if !self.check_enum(&next) {
+                return Err(parser.panic_current("Value is invalid for object enum"));
+            }
             self.add_value(next);
             Ok(())
         }
@@ -331,7 +876,9 @@ impl Parser {
             current: 0,
             next: if len == 0 { 0 } else { 1 },
             eof_token: TokenInfo::new(Token::Eof, (len, len)),
-            env: Environment::new()
+            env: Environment::new(),
+            warnings: Vec::new(),
+            check_paths: false,
         }
     }
 
@@ -349,7 +896,7 @@ impl Parser {
 
     pub fn advance(&mut self) -> bool {
         self.current = self.next;
-        return if self.next == self.tokens.len() {
+        if self.next == self.tokens.len() {
             false
         } else {
             self.next += 1;
@@ -366,23 +913,42 @@ impl Parser {
     }
 
     pub fn current(&self) -> &TokenInfo {
-        return if self.eof() { &self.eof_token } else { &self.tokens[self.current] };
+        if self.eof() { &self.eof_token } else { &self.tokens[self.current] }
     }
 
     pub fn next(&self) -> &TokenInfo {
-        return if self.next_eof() { &self.eof_token } else { &self.tokens[self.next] };
+        if self.next_eof() { &self.eof_token } else { &self.tokens[self.next] }
     }
 
     fn panic_expect(&self, exp: &str) -> ParserError {
-        ParserError::new(format!("unexpected '{}' at {}:{}. Expected '{}'", self.next().to_string(), 
+        ParserError::new(format!("unexpected '{}' at {}:{}. Expected '{}'", self.next(), 
             self.next().position().0, self.next().position().1, exp))
     }
 
     fn panic_current(&self, exp: &str) -> ParserError {
-        ParserError::new(format!("current '{}' at {}:{}. {}", self.current().to_string(), 
+        ParserError::new(format!("current '{}' at {}:{}. {}", self.current(), 
                 self.current().position().0, self.current().position().1, exp))
     }
 
+    fn warn_current(&mut self, exp: &str) {
+        let msg = format!("current '{}' at {}:{}. {}", self.current(), 
+                self.current().position().0, self.current().position().1, exp);
+        self.warnings.push(msg);
+    }
+
+    pub fn warnings(&self) -> &Vec<String> {
+        &self.warnings
+    }
+
+    // `must_exist` paths are checked against the local filesystem only when it is on
+    pub fn set_check_paths(&mut self, val: bool) {
+        self.check_paths = val;
+    }
+
+    pub fn check_paths(&self) -> bool {
+        self.check_paths
+    }
+
     pub fn expect<F: Fn(&Token) -> bool>(&mut self, call: &F) -> bool {
         if call(self.next().token()) {
             self.advance();
@@ -399,14 +965,14 @@ impl Parser {
             }
             result.make_array();
         }
-        return Ok(result);
+        Ok(result)
     }
 
     fn read_name(&mut self) -> (bool, String) {
         let name = match &self.next().token() {
             Token::Ident(value) => { Some(String::from(value)) },
             Token::String(value) => { Some(String::from(value)) },
-            _ => if self.next().literal().len() > 0 {
+            _ => if !self.next().literal().is_empty() {
                 Some(String::from(self.next().literal()))
             } else {
                 None
@@ -420,19 +986,42 @@ impl Parser {
 
     fn try_read_interval<T: helpers::ValueReadCheck + helpers::WithInterval>(&mut self, result: &mut T) -> Result<bool, ParserError> {
         if self.expect(&T::token_checker) {
-            result.set_min(self);
+            result.set_min(self)?;
+            if self.expect(&Token::is_special(SpecialToken::Less)) {
+                result.exclude_min();
+            }
             if !self.expect(&Token::is_special(SpecialToken::Interval)) {
                 return Err(self.panic_expect(".."));
             }
-            if self.expect(&T::token_checker) {
-                result.set_max(self);
-            }
+            self.try_read_interval_max(result)?;
             Ok(true)
         } else if self.expect(&Token::is_special(SpecialToken::Interval)) {
-            self.advance();
-            if self.expect(&T::token_checker) {
-                result.set_max(self);
+            self.try_read_interval_max(result)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn try_read_interval_max<T: helpers::ValueReadCheck + helpers::WithInterval>(&mut self, result: &mut T) -> Result<(), ParserError> {
+        let exclusive = self.expect(&Token::is_special(SpecialToken::Less));
+        if self.expect(&T::token_checker) {
+            result.set_max(self)?;
+            if exclusive {
+                result.exclude_max();
+            }
+        } else if exclusive {
+            return Err(self.panic_expect(T::expected()));
+        }
+        Ok(())
+    }
+
+    fn try_read_step<T: helpers::ValueReadCheck + helpers::WithStep>(&mut self, result: &mut T) -> Result<bool, ParserError> {
+        if self.expect(&Token::is_special(SpecialToken::Step)) {
+            if !self.expect(&T::token_checker) {
+                return Err(self.panic_expect(T::expected()));
             }
+            result.set_step(self)?;
             Ok(true)
         } else {
             Ok(false)
@@ -445,18 +1034,66 @@ impl Parser {
         if self.expect(&Token::is_special(SpecialToken::Enum)) {
             if self.expect(&Token::is_special(SpecialToken::LBrace)) {
                 while self.expect(&T::token_checker) {
-                    output.enum_add_value(self);
+                    // a repeated value is kept once with its first label
+                    let added = output.enum_add_value(self)?;
+                    if !added {
+                        self.warn_current("Enum value is already defined, the repeated one is ignored");
+                    }
+                    if let Some(label) = self.try_read_enum_label()? {
+                        if added {
+                            output.enum_set_label(&label);
+                        }
+                    }
                     self.expect(&Token::is_special(SpecialToken::Comma));
                 }
                 if !self.expect(&Token::is_special(SpecialToken::RBrace)) {
                     return Err(self.panic_expect(&(String::from("} or ") + T::expected())));
                 }
-                return Ok(true);
+                Ok(true)
             } else {
-                return Err(self.panic_expect(&(String::from("{"))));
+                Err(self.panic_expect(&(String::from("{"))))
             }
         } else {
-            return Ok(false);
+            Ok(false)
+        }
+    }
+
+    fn try_read_enum_label(&mut self) -> Result<Option<String>, ParserError> {
+        if self.expect(&Token::is_special(SpecialToken::Colon)) {
+            match self.next().token() {
+                Token::String(label) => {
+                    let label = label.clone();
+                    self.advance();
+                    Ok(Some(label))
+                },
+                _ => Err(self.panic_expect("label string")),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn try_read_any_enum(&mut self, output: &mut AnyType) -> Result<bool, ParserError> {
+        if self.expect(&Token::is_special(SpecialToken::Enum)) {
+            if !self.expect(&Token::is_special(SpecialToken::LBrace)) {
+                return Err(self.panic_expect("{"));
+            }
+            while !self.expect(&Token::is_special(SpecialToken::RBrace)) {
+                let val = self.guess_element()?;
+                let added = output.add_enum_value(val);
+                if !added {
+                    self.warn_current("Enum value is already defined, the repeated one is ignored");
+                }
+                if let Some(label) = self.try_read_enum_label()? {
+                    if added {
+                        output.set_enum_label(&label);
+                    }
+                }
+                self.expect(&Token::is_special(SpecialToken::Comma));
+            }
+            Ok(true)
+        } else {
+            Ok(false)
         }
     }
 
@@ -488,10 +1125,22 @@ impl Parser {
             Element::Boolean(v) => { self.read_value(v) },
             Element::String(v) => { self.read_value(v) },
             Element::Integer(v) => { self.read_value(v) },
+            Element::Unsigned(v) => { self.read_value(v) },
+            Element::BigInt(v) => { self.read_value(v) },
             Element::Floating(v) => { self.read_value(v) },
+            Element::Decimal(v) => { self.read_value(v) },
+            Element::Duration(v) => { self.read_value(v) },
+            Element::Size(v) => { self.read_value(v) },
+            Element::Date(v) => { self.read_value(v) },
+            Element::Time(v) => { self.read_value(v) },
+            Element::DateTime(v) => { self.read_value(v) },
+            Element::Net(v) => { self.read_value(v) },
+            Element::Semver(v) => { self.read_value(v) },
+            Element::Bytes(v) => { self.read_value(v) },
+            Element::Path(v) => { self.read_value(v) },
             Element::Object(v) => { self.read_value(v) },
             Element::Any(v) => {
-                *v = self.parse_any()?;
+                self.read_any_value(v)?;
                 Ok(())
             },
             Element::None => Err(self.panic_current("no object")),
@@ -509,17 +1158,47 @@ impl Parser {
         Ok(())
     }
 
-    fn parse_number<T: helpers::ValueReadCheck 
-            + ObjectBase 
-            + helpers::WithEnum
-            + helpers::WithInterval>(&mut self, mut result: T) -> Result<T, ParserError> {
+    fn parse_number<T>(&mut self, mut result: NumberType<T>) -> Result<NumberType<T>, ParserError> 
+        where T: Numeric,
+            NumberType<T>: helpers::ValueReadCheck 
+                + ObjectBase 
+                + helpers::WithEnum
+                + helpers::WithInterval
+                + helpers::WithStep {
 
         result = self.parse_begin(result)?;
 
-        while self.try_read_interval(&mut result)? || self.try_read_enum(&mut result)?{ }
+        while self.try_read_interval(&mut result)? 
+            || self.try_read_enum(&mut result)?
+            || self.try_read_step(&mut result)? { }
+
+        if let Some(val) = result.invalid_enum_value() {
+            return Err(self.panic_current(&format!("Enum value {} doesn't match the interval or step", val.to_string())));
+        }
 
         self.read_value(&mut result)?;
-        return Ok(result);
+        Ok(result)
+    }
+
+    // like numbers, but without a step: dates, times and versions
+    fn parse_ordered<T>(&mut self, mut result: NumberType<T>) -> Result<NumberType<T>, ParserError> 
+        where T: Numeric,
+            NumberType<T>: helpers::ValueReadCheck 
+                + ObjectBase 
+                + helpers::WithEnum
+                + helpers::WithInterval {
+
+        result = self.parse_begin(result)?;
+
+        while self.try_read_interval(&mut result)? 
+            || self.try_read_enum(&mut result)? { }
+
+        if let Some(val) = result.invalid_enum_value() {
+            return Err(self.panic_current(&format!("Enum value {} doesn't match the interval", val.to_string())));
+        }
+
+        self.read_value(&mut result)?;
+        Ok(result)
     }
 
     pub fn parse_integer(&mut self) -> Result<IntegerType, ParserError> {
@@ -533,7 +1212,7 @@ impl Parser {
     pub fn parse_boolean(&mut self) -> Result<BooleanType, ParserError> {
         let mut result = self.parse_begin(BooleanType::new())?;
         self.read_value(&mut result)?;
-        return Ok(result);
+        Ok(result)
     }
 
     pub fn parse_string(&mut self) -> Result<StringType, ParserError> {
@@ -543,6 +1222,82 @@ impl Parser {
         Ok(result)
     }
 
+    // the encoding comes from the field options: data(encoding: "hex"): bytes
+    pub fn parse_bytes(&mut self, opts: &Options) -> Result<BytesType, ParserError> {
+        let encoding = match (opts.get("encoding"), opts.get_string("encoding")) {
+            (_, Some(name)) => match Encoding::from_name(name) {
+                Some(val) => val,
+                None => return Err(self.panic_current(&format!("Invalid encoding '{}'. Use base64, base64url or hex", name))),
+            },
+            (Some(_), None) => return Err(self.panic_current("Invalid encoding, it should be a string. Use base64, base64url or hex")),
+            (None, None) => Encoding::Base64,
+        };
+        let mut result = self.parse_begin(BytesType::new(encoding))?;
+        // the length is an integer interval
+        let mut length = IntegerType::new();
+        if self.try_read_interval(&mut length)? {
+            result.set_length(length.interval().clone());
+        }
+        self.read_value(&mut result)?;
+        Ok(result)
+    }
+
+    // the rules come from the field options: key(absolute, extension: ".pem", must_exist): path
+    pub fn parse_path(&mut self, opts: &Options) -> Result<PathType, ParserError> {
+        let mut result = self.parse_begin(PathType::new())?;
+        if opts.has_bool("absolute") && opts.has_bool("relative") {
+            return Err(self.panic_current("Path can't be absolute and relative at the same time"));
+        }
+        if opts.has_bool("absolute") {
+            result.set_absolute();
+        }
+        if opts.has_bool("relative") {
+            result.set_relative();
+        }
+        if let Some(ext) = opts.get_string("extension") {
+            result.set_extension(ext);
+        }
+        if opts.has_bool("must_exist") {
+            result.set_must_exist();
+        }
+        self.read_value(&mut result)?;
+        Ok(result)
+    }
+
+    pub fn parse_net(&mut self, kind: NetKind) -> Result<NetType, ParserError> {
+        let mut result = self.parse_begin(NetType::new(kind))?;
+
+        while self.try_read_within(&mut result)?
+            || self.try_read_enum(&mut result)? { }
+
+        if let Some(val) = result.invalid_enum_value() {
+            return Err(self.panic_current(&format!("Enum value '{}' is not within the networks", val)));
+        }
+
+        self.read_value(&mut result)?;
+        Ok(result)
+    }
+
+    // within "10.0.0.0/8"
+    fn try_read_within(&mut self, result: &mut NetType) -> Result<bool, ParserError> {
+        if self.expect(&Token::is_special(SpecialToken::Within)) {
+            if !result.kind().has_within() {
+                return Err(self.panic_current(&format!("'within' is not supported for {}", result.kind().name())));
+            }
+            let network = match self.next().token() {
+                Token::String(val) => Network::parse(val),
+                _ => return Err(self.panic_expect("network string")),
+            };
+            self.advance();
+            match network {
+                Some(val) => Ok({ result.add_network(val); true }),
+                None => Err(self.panic_current("Invalid network. Expected address/prefix")),
+            }
+        } else {
+            Ok(false)
+        }
+    }
+
     pub fn parse_object(&mut self) -> Result<ObjectType, ParserError> {
         let mut result = self.parse_begin(ObjectType::new())?;
         if self.expect(&Token::is_special(SpecialToken::LBrace)) {
@@ -555,21 +1310,35 @@ impl Parser {
                 if self.expect(&Token::is_special(SpecialToken::Semicolon)) ||
                     self.expect(&Token::is_special(SpecialToken::Comma)) {}
             } 
+            self.try_read_enum(&mut result)?;
             self.read_value(&mut result)?;
         }
-        return Ok(result);
+        Ok(result)
     }
 
     // any is a very special case
     pub fn parse_any(&mut self) -> Result<AnyType, ParserError> {
         let mut result = AnyType::new();
+        if self.try_read_any_enum(&mut result)? {
+            self.read_any_value(&mut result)?;
+        } else if !self.read_any_value(&mut result)? {
+            return Err(self.panic_expect("= or :"));
+        }
+        Ok(result)
+    }
+
+    fn read_any_value(&mut self, result: &mut AnyType) -> Result<bool, ParserError> {
         if self.expect(&Token::is_special(SpecialToken::Equal)) ||
         self.expect(&Token::is_special(SpecialToken::Colon)) {
-            result.add_value(self.guess_element()?);
+            let val = self.guess_element()?;
+            if !result.check_enum(&val) {
+                return Err(self.panic_current("Value is invalid for any enum"));
+            }
+            result.add_value(val);
+            Ok(true)
         } else {
-            return Err(self.panic_expect("= or :"));
+            Ok(false)
         }
-        Ok(result)
     }
 
     fn parse_value_for<T: helpers::ValueReadCheck + ObjectBase>(&mut self, mut value: T) -> Result<T, ParserError> {
@@ -581,9 +1350,9 @@ impl Parser {
         let mut new_any = AnyType::new_array();
         while !self.expect(&Token::is_special(SpecialToken::RBracket)) {
             new_any.add_value(self.guess_element()?);
-            if self.expect(&Token::is_special(SpecialToken::Comma)) {}
+            self.expect(&Token::is_special(SpecialToken::Comma));
         }
-        return Ok(Element::Any(new_any));
+        Ok(Element::Any(new_any))
     }
  
     fn guess_object(&mut self) -> Result<ObjectType, ParserError> {
@@ -598,14 +1367,15 @@ impl Parser {
                 }
             }
 
-            if self.expect(&Token::is_special(SpecialToken::Equal)) || 
-                self.expect(&Token::is_special(SpecialToken::Colon)) {}
+            if !self.expect(&Token::is_special(SpecialToken::Equal)) {
+                self.expect(&Token::is_special(SpecialToken::Colon));
+            }
 
             let opts = Options::new();
             next.add_field(FieldType::new(field_name, self.guess_element()?, opts));
             self.expect(&Token::is_special(SpecialToken::Comma));
         }
-        return Ok(next);
+        Ok(next)
     }
 
     fn guess_number(&mut self) -> Result<Element, ParserError> {
@@ -616,6 +1386,10 @@ impl Parser {
                 self.restore(&bu); 
                 Ok(Element::Integer(self.parse_value_for(IntegerType::new())?))
             },
+            Token::BigInteger(_) => { 
+                self.restore(&bu); 
+                Ok(Element::BigInt(self.parse_value_for(BigIntType::new())?))
+            },
             Token::Floating(_) => { 
                 self.restore(&bu); 
                 Ok(Element::Floating(self.parse_value_for(FloatingType::new())?)) 
@@ -627,7 +1401,14 @@ impl Parser {
     fn guess_element(&mut self) -> Result<Element, ParserError> {
         match &self.next().token() {
             Token::Integer(_) => Ok(Element::Integer(self.parse_value_for(IntegerType::new())?)),
+            Token::BigInteger(_) => Ok(Element::BigInt(self.parse_value_for(BigIntType::new())?)),
             Token::Floating(_) => Ok(Element::Floating(self.parse_value_for(FloatingType::new())?)),
+            Token::Duration(_) => Ok(Element::Duration(self.parse_value_for(DurationType::new())?)),
+            Token::Size(..) => Ok(Element::Size(self.parse_value_for(SizeType::new())?)),
+            Token::Date(_) => Ok(Element::Date(self.parse_value_for(DateType::new())?)),
+            Token::Time(_) => Ok(Element::Time(self.parse_value_for(TimeType::new())?)),
+            Token::DateTime(_) => Ok(Element::DateTime(self.parse_value_for(DateTimeType::new())?)),
+            Token::Version(_) => Ok(Element::Semver(self.parse_value_for(SemverType::new())?)),
             Token::Boolean(_) => Ok(Element::Boolean(self.parse_value_for(BooleanType::new())?)),
             Token::String(_) => Ok(Element::String(self.parse_value_for(StringType::new())?)),
             Token::Special(v) => match v {
@@ -653,8 +1434,9 @@ impl Parser {
         }
     }
 
-    fn try_read_options(&mut self) -> Result<Options, ParserError> {
+    fn try_read_options(&mut self) -> Result<(Options, Option<ParserState>), ParserError> {
         let mut result = Options::new();
+        let mut examples = None;
         if self.expect(&Token::is_special(SpecialToken::LParen)) {
             while !self.expect(&Token::is_special(SpecialToken::RParen)) {
                 let (found, name) = self.read_name();
@@ -663,6 +1445,10 @@ impl Parser {
                 }
 
                 if self.expect(&Token::is_special(SpecialToken::Equal)) || self.expect(&Token::is_special(SpecialToken::Colon)) {
+                    // examples are read again when the type of the field is known
+                    if name == "examples" {
+                        examples = Some(self.backup());
+                    }
                     let element = self.guess_element()?;
                     match element {
                         Element::None => { result.add(&name, Element::Boolean(BooleanType::from(true))) },
@@ -674,30 +1460,101 @@ impl Parser {
                 self.expect(&Token::is_special(SpecialToken::Comma));
             }
         }
+        Ok((result, examples))
+    }
+
+    fn read_example<T: helpers::ValueReadCheck + ObjectBase + Clone>(&mut self, template: &T) -> Result<T, ParserError> {
+        let mut result = helpers::create_same_object(template);
+        self.read_value_nocheck(&mut result)?;
         Ok(result)
     }
 
+    fn read_element_example(&mut self, element: &Element) -> Result<Element, ParserError> {
+        match element {
+            Element::Boolean(v) => Ok(Element::Boolean(self.read_example(v)?)),
+            Element::String(v) => Ok(Element::String(self.read_example(v)?)),
+            Element::Integer(v) => Ok(Element::Integer(self.read_example(v)?)),
+            Element::Unsigned(v) => Ok(Element::Unsigned(self.read_example(v)?)),
+            Element::BigInt(v) => Ok(Element::BigInt(self.read_example(v)?)),
+            Element::Floating(v) => Ok(Element::Floating(self.read_example(v)?)),
+            Element::Decimal(v) => Ok(Element::Decimal(self.read_example(v)?)),
+            Element::Duration(v) => Ok(Element::Duration(self.read_example(v)?)),
+            Element::Size(v) => Ok(Element::Size(self.read_example(v)?)),
+            Element::Date(v) => Ok(Element::Date(self.read_example(v)?)),
+            Element::Time(v) => Ok(Element::Time(self.read_example(v)?)),
+            Element::DateTime(v) => Ok(Element::DateTime(self.read_example(v)?)),
+            Element::Net(v) => Ok(Element::Net(self.read_example(v)?)),
+            Element::Semver(v) => Ok(Element::Semver(self.read_example(v)?)),
+            Element::Bytes(v) => Ok(Element::Bytes(self.read_example(v)?)),
+            Element::Path(v) => Ok(Element::Path(self.read_example(v)?)),
+            Element::Object(v) => Ok(Element::Object(self.read_example(v)?)),
+            Element::Any(v) => {
+                let val = self.guess_element()?;
+                if !v.check_enum(&val) {
+                    return Err(self.panic_current("Example is invalid for any enum"));
+                }
+                Ok(val)
+            },
+            Element::None => Err(self.panic_current("no object")),
+        }
+    }
+
+    // every example is read as a value of the field, so enums and intervals are checked
+    fn read_examples(&mut self, element: &Element, at: &ParserState) -> Result<Element, ParserError> {
+        let bu = self.backup();
+        self.restore(at);
+        if !self.expect(&Token::is_special(SpecialToken::LBracket)) {
+            return Err(self.panic_expect("["));
+        }
+        let mut result = AnyType::new_array();
+        while !self.expect(&Token::is_special(SpecialToken::RBracket)) {
+            result.add_value(self.read_element_example(element)?);
+            self.expect(&Token::is_special(SpecialToken::Comma));
+        }
+        self.restore(&bu);
+        Ok(Element::Any(result))
+    }
+
     pub fn parse_field(&mut self) -> Result<FieldType, ParserError> {
+        let position = self.next().position();
         let (_, name) = self.read_name();
-        let opts = self.try_read_options()?;
-        if name.len() > 0 && !self.expect(&Token::is_special(SpecialToken::Colon)) {
+        let (mut opts, examples) = self.try_read_options()?;
+        if !name.is_empty() && !self.expect(&Token::is_special(SpecialToken::Colon)) {
             return Err(self.panic_expect(":"));
         }
         self.advance();
+        let mut alias = None;
         let element = match &self.current().token() {
             Token::Type(name) => match name {
                 TypeName::TypeString => Element::String(self.parse_string()?),
                 TypeName::TypeInteger => Element::Integer(self.parse_integer()?),
+                TypeName::TypeUnsigned => Element::Unsigned(self.parse_number(UnsignedType::new())?),
+                TypeName::TypeBigInt => Element::BigInt(self.parse_ordered(BigIntType::new())?),
                 TypeName::TypeFloating => Element::Floating(self.parse_floating()?),
+                TypeName::TypeDecimal => Element::Decimal(self.parse_number(DecimalType::new())?),
                 TypeName::TypeBoolean => Element::Boolean(self.parse_boolean()?),
                 TypeName::TypeObject => Element::Object(self.parse_object()?),
                 TypeName::TypeAny => Element::Any(self.parse_any()?),
+                TypeName::TypeDuration => Element::Duration(self.parse_number(DurationType::new())?),
+                TypeName::TypeSize => Element::Size(self.parse_number(SizeType::new())?),
+                TypeName::TypeDate => Element::Date(self.parse_ordered(DateType::new())?),
+                TypeName::TypeTime => Element::Time(self.parse_ordered(TimeType::new())?),
+                TypeName::TypeDateTime => Element::DateTime(self.parse_ordered(DateTimeType::new())?),
+                TypeName::TypeIp => Element::Net(self.parse_net(NetKind::Ip)?),
+                TypeName::TypeIpv4 => Element::Net(self.parse_net(NetKind::Ipv4)?),
+                TypeName::TypeIpv6 => Element::Net(self.parse_net(NetKind::Ipv6)?),
+                TypeName::TypeCidr => Element::Net(self.parse_net(NetKind::Cidr)?),
+                TypeName::TypeEndpoint => Element::Net(self.parse_net(NetKind::Endpoint)?),
+                TypeName::TypeSemver => Element::Semver(self.parse_ordered(SemverType::new())?),
+                TypeName::TypeBytes => Element::Bytes(self.parse_bytes(&opts)?),
+                TypeName::TypePath => Element::Path(self.parse_path(&opts)?),
             },
             Token::Ident(name)
             | Token::String(name) => {
                 match self.env.get_alias(name) {
                     Some(element) => { 
                         let mut val = element.clone();
+                        alias = Some(name.clone());
                         self.read_element_value(&mut val)?;
                         val
                     },
@@ -706,7 +1563,16 @@ impl Parser {
             }
             _ => { return Err(self.panic_current("should be a typename")); }
         };
-        Ok(FieldType::new(name, element, opts))
+        if let Some(at) = examples {
+            let values = self.read_examples(&element, &at)?;
+            opts.add("examples", values);
+        }
+        let mut field = FieldType::new(name, element, opts);
+        field.set_position(position);
+        if let Some(alias) = alias {
+            field.set_alias(alias);
+        }
+        Ok(field)
     }
 
     fn read_path(&mut self) -> Result<Vec<String>, ParserError> {
@@ -723,7 +1589,86 @@ impl Parser {
                 }
             }
         }
-        return Ok(res);
+        Ok(res)
+    }
+
+    // `profile` is a name of a field unless another name follows it
+    fn is_profile(&mut self) -> bool {
+        let bu = self.backup();
+        self.advance();
+        let res = matches!(self.next().token(), Token::Ident(_) | Token::String(_));
+        self.restore(&bu);
+        res
+    }
+
+    // the field of the module at `db.pool`, paths don't go through arrays
+    fn profile_target(&self, module: &Module, path: &[String]) -> Result<FieldType, ParserError> {
+        let not_found = || self.panic_current(&format!("Path '{}' doesn't match a field of the module", path.join(".")));
+        let mut fld = module.get_field(&path[0]).ok_or_else(not_found)?;
+        for name in &path[1..] {
+            fld = match fld.value() {
+                Element::Object(obj) if !obj.value().is_array() => obj.get_field(name).ok_or_else(not_found)?,
+                _ => return Err(not_found()),
+            };
+        }
+        Ok(fld.clone())
+    }
+
+    // profile prod: base { db.pool = 50, log.level = "warn" }
+    fn parse_profile(&mut self, module: &Module) -> Result<Profile, ParserError> {
+        self.advance();
+        let (_, name) = self.read_name();
+        if module.get_profile(&name).is_some() {
+            return Err(self.panic_current(&format!("Profile '{}' is already defined", name)));
+        }
+        let mut parent = None;
+        if self.expect(&Token::is_special(SpecialToken::Colon)) {
+            let (found, parent_name) = self.read_name();
+            if !found {
+                return Err(self.panic_expect("profile name"));
+            }
+            // parents are defined before, so profiles can't inherit each other in a loop
+            if module.get_profile(&parent_name).is_none() {
+                return Err(self.panic_current(&format!("Profile '{}' is not defined", parent_name)));
+            }
+            parent = Some(parent_name);
+        }
+        if !self.expect(&Token::is_special(SpecialToken::LBrace)) {
+            return Err(self.panic_expect("{"));
+        }
+        let mut result = Profile::new(name, parent);
+        while !self.expect(&Token::is_special(SpecialToken::RBrace)) {
+            let path = self.read_path()?;
+            if path.is_empty() {
+                return Err(self.panic_expect("field path or }"));
+            }
+            let target = self.profile_target(module, &path)?;
+            if target.is_deprecated() {
+                self.warn_current(&match target.deprecation_hint() {
+                    Some(hint) => format!("Field '{}' is deprecated: {}", path.join("."), hint),
+                    None => format!("Field '{}' is deprecated", path.join(".")),
+                });
+            }
+            if !self.expect(&Token::is_special(SpecialToken::Equal)) &&
+                !self.expect(&Token::is_special(SpecialToken::Colon)) {
+                return Err(self.panic_expect("="));
+            }
+            // values are read as the values of the field, so enums and intervals are checked
+            let element = match target.value() {
+                Element::Any(v) => {
+                    let mut val = v.clone();
+                    val.set_value(PossibleArray::Value(Box::new(None)));
+                    val.add_value(self.read_element_example(target.value())?);
+                    Element::Any(val)
+                },
+                other => self.read_element_example(other)?,
+            };
+            let mut fld = FieldType::new(target.name().to_string(), element, target.options().clone());
+            fld.set_position(target.position());
+            result.add_value(path, fld);
+            self.expect(&Token::is_special(SpecialToken::Comma));
+        }
+        Ok(result)
     }
 
     pub fn parse_module(&mut self) -> Result<Module, ParserError> {
@@ -734,7 +1679,12 @@ impl Parser {
         }
 
         while !self.eof() {
+            let profile = matches!(self.next().token(), Token::Ident(name) if name == "profile") && self.is_profile();
             match self.next().token() {
+                Token::Ident(_) if profile => {
+                    let profile = self.parse_profile(&res)?;
+                    res.add_profile(profile);
+                },
                 Token::Ident(_) 
                 | Token::String(_) => {
                     let fld = self.parse_field()?;
@@ -742,14 +1692,15 @@ impl Parser {
                 },
                 Token::Special(SpecialToken::Alias) => {
                     self.advance();
-                    let mut fld = self.parse_field()?;
+                    let fld = self.parse_field()?;
                     self.env.set_alias(fld.name(), fld.value().clone());
+                    res.add_alias(fld);
                 },
                 Token::Eof => break,
                 _ => return Err(self.panic_expect("name, alias")),
             }
         }
 
-        return Ok(res);
+        Ok(res)
     }
 }
@@ -13,7 +13,7 @@ pub struct Scanner<'a> {
 
 impl<'a> Scanner<'a> {
 
-    pub fn new (value: &str) -> Scanner {
+    pub fn new (value: &str) -> Scanner<'_> {
         Scanner {
             current: value,
             top: utils::get_top(value),
@@ -50,10 +50,10 @@ impl<'a> Scanner<'a> {
         while !self.eol() && predic(self.top()) {
             count += self.advance();
         }
-        return count;
+        count
     }
     pub fn advance(&mut self) -> usize {
-        if self.current.len() > 0 {
+        if !self.current.is_empty() {
             let c: char = self.top;
             let shift = c.len_utf8();
             self.current = &self.current[shift..];
@@ -64,15 +64,15 @@ impl<'a> Scanner<'a> {
             };
             return shift;
         } 
-        return 0;
+        0
     }
 
     pub fn eol(&self) -> bool {
-        return self.current.len() == 0;
+        self.current.is_empty()
     }
 
     pub fn backup(&self) -> Scanner<'a> {
-        return Scanner {
+        Scanner {
             current: self.current,
             top: self.top,
             position: self.position
@@ -0,0 +1,192 @@
+use std::cmp::Ordering;
+use super::helpers::Numeric;
+
+// SemVer 2.0 regex without backslashes, so it can be written to json as is
+pub const SEMVER_PATTERN: &str = "^(0|[1-9][0-9]*)[.](0|[1-9][0-9]*)[.](0|[1-9][0-9]*)\
+(-((0|[1-9][0-9]*|[0-9]*[a-zA-Z-][0-9a-zA-Z-]*)([.](0|[1-9][0-9]*|[0-9]*[a-zA-Z-][0-9a-zA-Z-]*))*))?\
+([+][0-9a-zA-Z-]+([.][0-9a-zA-Z-]+)*)?$";
+
+mod utils {
+    pub fn is_ident_char(c: u8) -> bool {
+        c.is_ascii_alphanumeric() || c == b'-'
+    }
+
+    pub fn digits_end(data: &[u8], at: usize) -> usize {
+        let mut end = at;
+        while end < data.len() && data[end].is_ascii_digit() {
+            end += 1;
+        }
+        end
+    }
+
+    // dot separated identifiers: alpha.1
+    pub fn idents_end(data: &[u8], at: usize) -> usize {
+        let mut end = at;
+        loop {
+            let begin = end;
+            while end < data.len() && is_ident_char(data[end]) {
+                end += 1;
+            }
+            if end == begin {
+                return begin;
+            }
+            if data.get(end) == Some(&b'.') && data.get(end + 1).is_some_and(|c| is_ident_char(*c)) {
+                end += 1;
+            } else {
+                return end;
+            }
+        }
+    }
+
+    pub fn is_number(data: &str) -> bool {
+        !data.is_empty() && data.bytes().all(|c| c.is_ascii_digit())
+    }
+
+    // numbers don't have leading zeros
+    pub fn parse_number(data: &str) -> Option<u64> {
+        if !is_number(data) || (data.len() > 1 && data.starts_with('0')) {
+            None
+        } else {
+            data.parse::<u64>().ok()
+        }
+    }
+}
+
+// length of a version literal (1.2.0, 1.0.0-alpha.1+build.5) at the beginning of the string,
+// 0 if there is no literal
+pub fn literal_len(data: &str) -> usize {
+    let bytes = data.as_bytes();
+    let mut end = 0;
+    for id in 0..3 {
+        if id > 0 {
+            if bytes.get(end) != Some(&b'.') {
+                return 0;
+            }
+            end += 1;
+        }
+        let next = utils::digits_end(bytes, end);
+        if next == end {
+            return 0;
+        }
+        end = next;
+    }
+    for prefix in [b'-', b'+'] {
+        if bytes.get(end) == Some(&prefix) {
+            let next = utils::idents_end(bytes, end + 1);
+            if next > end + 1 {
+                end = next;
+            }
+        }
+    }
+    end
+}
+
+#[derive(Clone)]
+pub struct Version {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre: Vec<String>,
+    build: Vec<String>,
+}
+
+impl Version {
+    pub fn parse(data: &str) -> Result<Version, String> {
+        let invalid = || format!("Invalid semantic version '{}'", data);
+        let (rest, build) = match data.split_once('+') {
+            Some((rest, build)) => (rest, build.split('.').map(String::from).collect::<Vec<String>>()),
+            None => (data, Vec::new()),
+        };
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) => (core, pre.split('.').map(String::from).collect::<Vec<String>>()),
+            None => (rest, Vec::new()),
+        };
+        let numbers = core.split('.').map(utils::parse_number).collect::<Vec<Option<u64>>>();
+        if numbers.len() != 3 || numbers.iter().any(|n| n.is_none()) {
+            return Err(invalid());
+        }
+        let valid_ident = |v: &String| !v.is_empty() && v.bytes().all(utils::is_ident_char);
+        if !pre.iter().all(valid_ident) || !build.iter().all(valid_ident) {
+            return Err(invalid());
+        }
+        if pre.iter().any(|v| utils::is_number(v) && utils::parse_number(v).is_none()) {
+            return Err(format!("Numeric pre-release identifiers should not have leading zeros in '{}'", data));
+        }
+        Ok(Version {
+            major: numbers[0].unwrap_or(0),
+            minor: numbers[1].unwrap_or(0),
+            patch: numbers[2].unwrap_or(0),
+            pre,
+            build,
+        })
+    }
+
+    // numeric identifiers are less than alphanumeric ones
+    fn compare_ident(a: &str, b: &str) -> Ordering {
+        match (a.parse::<u64>(), b.parse::<u64>()) {
+            (Ok(x), Ok(y)) if utils::is_number(a) && utils::is_number(b) => x.cmp(&y),
+            _ if utils::is_number(a) => Ordering::Less,
+            _ if utils::is_number(b) => Ordering::Greater,
+            _ => a.cmp(b),
+        }
+    }
+
+    // precedence from SemVer 2.0, build metadata is ignored
+    fn precedence(&self, other: &Version) -> Ordering {
+        let core = (self.major, self.minor, self.patch).cmp(&(other.major, other.minor, other.patch));
+        if core != Ordering::Equal {
+            return core;
+        }
+        match (self.pre.is_empty(), other.pre.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => {
+                for (a, b) in self.pre.iter().zip(other.pre.iter()) {
+                    let res = Version::compare_ident(a, b);
+                    if res != Ordering::Equal {
+                        return res;
+                    }
+                }
+                self.pre.len().cmp(&other.pre.len())
+            },
+        }
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.precedence(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.precedence(other))
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre.join("."))?;
+        }
+        if !self.build.is_empty() {
+            write!(f, "+{}", self.build.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+impl Numeric for Version {
+    fn zero() -> Self {
+        Version { major: 0, minor: 0, patch: 0, pre: Vec::new(), build: Vec::new() }
+    }
+    fn name() -> &'static str {
+        "semver"
+    }
+    fn is_multiple_of(&self, _: &Self) -> bool {
+        true
+    }
+}
@@ -2,14 +2,22 @@
 use super::objects::*;
 use super::object_base::*;
 use super::helpers::*;
-use super::formatting::{array_format, element_format};
+use super::datetime::{Date, Time, DateTime};
+use super::network::NetKind;
+use super::semver::SEMVER_PATTERN;
+use super::datetime::TIME_PATTERN;
+use super::bigint::BigInt;
+use super::json::pointer_token;
+use super::decimal::{Float, Decimal};
+use super::formatting::{Formatting, UnitFormat, array_format, element_format, format};
+use super::formatting::format::ValueToString;
 
 
 mod to_json_schema {
     use super::*;
 
     pub trait SchemaToValues {
-        fn value_to_schema(&self, opts: &Options) -> Element;
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element;
     }
 
     trait SchField {
@@ -28,11 +36,17 @@ mod to_json_schema {
         }
     }
 
+    impl SchField for String {
+        fn value(self) -> Element {
+            Element::String(StringType::from(&self))
+        }
+    }
+
     impl SchField for &Vec<String> {
         fn value(self) -> Element {
             let mut arr = StringType::new_array();
             for v in self {
-                arr.add_value(&v);
+                arr.add_value(v);
             };
             Element::String(arr)
         }
@@ -56,15 +70,63 @@ mod to_json_schema {
             Element::Integer(arr)
         }
     }
-    impl SchField for &Vec<f64> {
+    impl SchField for &Vec<u64> {
         fn value(self) -> Element {
-            let mut arr = FloatingType::new_array();
+            let mut arr = UnsignedType::new_array();
             for v in self {
                 arr.add_value(*v);
             };
+            Element::Unsigned(arr)
+        }
+    }
+    impl SchField for &Vec<BigInt> {
+        fn value(self) -> Element {
+            let mut arr = BigIntType::new_array();
+            for v in self {
+                arr.add_value(v.clone());
+            };
+            Element::BigInt(arr)
+        }
+    }
+    impl SchField for &Vec<Float> {
+        fn value(self) -> Element {
+            let mut arr = FloatingType::new_array();
+            for v in self {
+                arr.add_value(v.clone());
+            };
             Element::Floating(arr)
         }
     }
+    impl SchField for &Vec<Decimal> {
+        fn value(self) -> Element {
+            let mut arr = DecimalType::new_array();
+            for v in self {
+                arr.add_value(v.clone());
+            };
+            Element::Decimal(arr)
+        }
+    }
+
+    impl SchField for &Vec<ObjectType> {
+        fn value(self) -> Element {
+            let mut arr = ObjectType::new();
+            arr.make_array();
+            for v in self {
+                arr.add_value(v.clone());
+            };
+            Element::Object(arr)
+        }
+    }
+
+    impl SchField for &Vec<Element> {
+        fn value(self) -> Element {
+            let mut arr = AnyType::new_array();
+            for v in self {
+                arr.add_value(v.clone());
+            };
+            Element::Any(arr)
+        }
+    }
 
     impl SchField for ObjectType {
         fn value(self) -> Element {
@@ -72,18 +134,36 @@ mod to_json_schema {
         }
     }
 
-    impl SchField for f64 {
+    impl SchField for Float {
         fn value(self) -> Element {
             Element::Floating(FloatingType::from(self))
         }
     }
 
+    impl SchField for Decimal {
+        fn value(self) -> Element {
+            Element::Decimal(DecimalType::from(self))
+        }
+    }
+
     impl SchField for i64 {
         fn value(self) -> Element {
             Element::Integer(IntegerType::from(self))
         }
     }
 
+    impl SchField for u64 {
+        fn value(self) -> Element {
+            Element::Unsigned(UnsignedType::from(self))
+        }
+    }
+
+    impl SchField for BigInt {
+        fn value(self) -> Element {
+            Element::BigInt(BigIntType::from(self))
+        }
+    }
+
     impl SchField for bool {
         fn value(self) -> Element {
             Element::Boolean(BooleanType::from(self))
@@ -98,22 +178,118 @@ mod to_json_schema {
         val.value()
     }
 
-    fn set_common_schema_options(obj: &mut ObjectType, opts: &Options) {
+    fn set_common_schema_options(obj: &mut ObjectType, opts: &Options, format: &Formatting) {
         if opts.has_bool("readonly") {
-            obj.add_field(field("readonly", true))
+            obj.add_field(field("readOnly", true))
+        }
+        if opts.has_bool("secret") {
+            obj.add_field(field("writeOnly", true));
+            obj.add_field(field("x-secret", true));
+        }
+        if let Some(examples) = opts.get("examples") {
+            obj.add_field(field("examples", examples.clone()));
+        }
+        if let Some(hint) = opts.get_string("deprecated") {
+            obj.add_field(field("deprecated", true));
+            obj.add_field(field("description", format!("Deprecated: {}", hint)));
+        } else if opts.has_bool("deprecated") {
+            obj.add_field(field("deprecated", true));
+        }
+    }
+
+    fn set_enum_schema_options<T>(obj: &mut ObjectType, vals: &Enum<T>, format: &Formatting) 
+        where T: SchField + PartialEq + Clone, 
+            for<'a> &'a Vec<T>: SchField {
+        if vals.has_labels() {
+            let mut one_of = ObjectType::new();
+            one_of.make_array();
+            for (id, v) in vals.values().iter().enumerate() {
+                let mut item = ObjectType::new();
+                if format.draft().has_const() {
+                    item.add_field(field("const", v.clone()));
+                } else {
+                    item.add_field(field("enum", &vec![v.clone()]));
+                }
+                if let Some(label) = vals.label(id) {
+                    item.add_field(field("title", label));
+                }
+                one_of.add_value(item);
+            }
+            obj.add_field(field("oneOf", one_of));
+        } else {
+            obj.add_field(field("enum", vals.values()));
+        }
+    }
+
+    // `(value, exclusive)` pairs of the bounds
+    fn set_bound_options<V: SchField>(obj: &mut ObjectType, min: Option<(V, bool)>, max: Option<(V, bool)>, format: &Formatting) {
+        let boolean_exclusive = format.draft().has_boolean_exclusive();
+        for (bound, name, exclusive_name) in [(min, "minimum", "exclusiveMinimum"), (max, "maximum", "exclusiveMaximum")] {
+            match bound {
+                Some((val, true)) if boolean_exclusive => {
+                    obj.add_field(field(name, val));
+                    obj.add_field(field(exclusive_name, true));
+                },
+                Some((val, exclusive)) => obj.add_field(field(if exclusive { exclusive_name } else { name }, val)),
+                None => {},
+            }
+        }
+    }
+
+    // a bound and whether it is exclusive
+    type Bound<V> = Option<(V, bool)>;
+
+    fn interval_bounds<T, V, F>(interval: &Interval<T>, convert: F) -> (Bound<V>, Bound<V>)
+        where T: Numeric, F: Fn(&T) -> V {
+        let min = if interval.has_min() { Some((convert(&interval.min(T::zero())), interval.is_min_exclusive())) } else { None };
+        let max = if interval.has_max() { Some((convert(&interval.max(T::zero())), interval.is_max_exclusive())) } else { None };
+        (min, max)
+    }
+
+    fn set_number_schema_options<T>(obj: &mut ObjectType, number: &NumberType<T>, format: &Formatting) 
+        where T: Numeric + SchField {
+        let (min, max) = interval_bounds(number.interval(), |v| v.clone());
+        set_bound_options(obj, min, max, format);
+        if let Some(step) = number.step() {
+            obj.add_field(field("multipleOf", step));
+        }
+    }
+
+    // json schema has no bounds for strings, so they go to the x- keywords
+    fn set_string_interval_options<T, F>(obj: &mut ObjectType, interval: &Interval<T>, as_string: F) 
+        where T: Numeric, F: Fn(&T) -> String {
+        if interval.has_min() {
+            let name = if interval.is_min_exclusive() { "x-exclusiveMinimum" } else { "x-minimum" };
+            obj.add_field(field(name, as_string(&interval.min(T::zero()))));
+        }
+        if interval.has_max() {
+            let name = if interval.is_max_exclusive() { "x-exclusiveMaximum" } else { "x-maximum" };
+            obj.add_field(field(name, as_string(&interval.max(T::zero()))));
         }
     }
 
     /// TODO: alot of copy-paste 
     impl SchemaToValues for Element {
-        fn value_to_schema(&self, opts: &Options) -> Element {
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element {
             match self {
-                Element::Boolean(v) => { to_json_schema_impl(v, opts) },
-                Element::String(v) => { to_json_schema_impl(v, opts) },
-                Element::Integer(v) => { to_json_schema_impl(v, opts) },
-                Element::Floating(v) => { to_json_schema_impl(v, opts) },
-                Element::Object(v) => { to_json_schema_impl(v, opts) },
-                Element::Any(v) => { to_json_schema_impl(v, opts) },
+                Element::Boolean(v) => { to_json_schema_impl(v, opts, format) },
+                Element::String(v) => { to_json_schema_impl(v, opts, format) },
+                Element::Integer(v) => { to_json_schema_impl(v, opts, format) },
+                Element::Unsigned(v) => { to_json_schema_impl(v, opts, format) },
+                Element::BigInt(v) => { to_json_schema_impl(v, opts, format) },
+                Element::Floating(v) => { to_json_schema_impl(v, opts, format) },
+                Element::Decimal(v) => { to_json_schema_impl(v, opts, format) },
+                Element::Duration(v) => { to_json_schema_impl(v, opts, format) },
+                Element::Size(v) => { to_json_schema_impl(v, opts, format) },
+                Element::Date(v) => { to_json_schema_impl(v, opts, format) },
+                Element::Time(v) => { to_json_schema_impl(v, opts, format) },
+                Element::DateTime(v) => { to_json_schema_impl(v, opts, format) },
+                Element::Net(v) => { to_json_schema_impl(v, opts, format) },
+                Element::Semver(v) => { to_json_schema_impl(v, opts, format) },
+                Element::Bytes(v) => { to_json_schema_impl(v, opts, format) },
+                Element::Path(v) => { to_json_schema_impl(v, opts, format) },
+                Element::Object(v) => { to_json_schema_impl(v, opts, format) },
+                Element::Any(v) => { to_json_schema_impl(v, opts, format) },
                 //Element::None => "".to_string(),
                 _ => Element::None,
             }
@@ -121,147 +297,422 @@ mod to_json_schema {
     }
 
     impl SchemaToValues for BooleanType {
-        fn value_to_schema(&self, opts: &Options) -> Element {
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element {
             let mut obj = ObjectType::new();
             obj.add_field(field("type", "boolean"));
             if self.is_array() {
                 let mut arr = ObjectType::new();
-                set_common_schema_options(&mut arr, opts);
+                set_common_schema_options(&mut arr, opts, format);
                 arr.add_field(field("type", "array"));
                 arr.add_field(field("items", obj));
-                return value(arr);
+                value(arr)
             } else {
-                set_common_schema_options(&mut obj, opts);
-                return value(obj);
+                set_common_schema_options(&mut obj, opts, format);
+                value(obj)
             }
         }
     }
 
     impl SchemaToValues for IntegerType {
-        fn value_to_schema(&self, opts: &Options) -> Element {
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element {
             let mut obj = ObjectType::new();
             obj.add_field(field("type", "integer"));
-            match self.enum_values() {
-                Some(vals) => obj.add_field(field("enum", vals.values())),
-                None => {},
+            if let Some(vals) = self.enum_values() {
+                set_enum_schema_options(&mut obj, vals, format);
+            }
+            set_number_schema_options(&mut obj, self, format);
+            if self.is_array() {
+                let mut arr = ObjectType::new();
+                set_common_schema_options(&mut arr, opts, format);
+                arr.add_field(field("type", "array"));
+                arr.add_field(field("items", obj));
+                value(arr)
+            } else {
+                set_common_schema_options(&mut obj, opts, format);
+                value(obj)
             }
-            if self.interval().has_min() {
-                obj.add_field(field("minimum", self.interval().min(0)));
+        }
+    }
+
+    impl SchemaToValues for UnsignedType {
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element {
+            let mut obj = ObjectType::new();
+            obj.add_field(field("type", "integer"));
+            if let Some(vals) = self.enum_values() {
+                set_enum_schema_options(&mut obj, vals, format);
             }
-            if self.interval().has_max() {
-                obj.add_field(field("maximum", self.interval().max(0)));
+            if !self.interval().has_min() {
+                obj.add_field(field("minimum", 0_u64));
             }
+            set_number_schema_options(&mut obj, self, format);
             if self.is_array() {
                 let mut arr = ObjectType::new();
-                set_common_schema_options(&mut arr, opts);
+                set_common_schema_options(&mut arr, opts, format);
                 arr.add_field(field("type", "array"));
                 arr.add_field(field("items", obj));
-                return value(arr);
+                value(arr)
             } else {
-                set_common_schema_options(&mut obj, opts);
-                return value(obj);
+                set_common_schema_options(&mut obj, opts, format);
+                value(obj)
+            }
+        }
+    }
+
+    // the values are written with all the digits, json parsers may round them
+    impl SchemaToValues for BigIntType {
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element {
+            let mut obj = ObjectType::new();
+            obj.add_field(field("type", "integer"));
+            if let Some(vals) = self.enum_values() {
+                set_enum_schema_options(&mut obj, vals, format);
+            }
+            set_number_schema_options(&mut obj, self, format);
+            if self.is_array() {
+                let mut arr = ObjectType::new();
+                set_common_schema_options(&mut arr, opts, format);
+                arr.add_field(field("type", "array"));
+                arr.add_field(field("items", obj));
+                value(arr)
+            } else {
+                set_common_schema_options(&mut obj, opts, format);
+                value(obj)
+            }
+        }
+    }
+
+    // bounds and steps are written with all the digits: "multipleOf": 0.01
+    impl SchemaToValues for DecimalType {
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element {
+            let mut obj = ObjectType::new();
+            obj.add_field(field("type", "number"));
+            if let Some(vals) = self.enum_values() {
+                set_enum_schema_options(&mut obj, vals, format);
+            }
+            set_number_schema_options(&mut obj, self, format);
+            if self.is_array() {
+                let mut arr = ObjectType::new();
+                set_common_schema_options(&mut arr, opts, format);
+                arr.add_field(field("type", "array"));
+                arr.add_field(field("items", obj));
+                value(arr)
+            } else {
+                set_common_schema_options(&mut obj, opts, format);
+                value(obj)
             }
         }
     }
 
     impl SchemaToValues for FloatingType {
-        fn value_to_schema(&self, opts: &Options) -> Element {
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element {
             let mut obj = ObjectType::new();
             obj.add_field(field("type", "number"));
-            match self.enum_values() {
-                Some(vals) => obj.add_field(field("enum", vals.values())),
-                None => {},
+            if let Some(vals) = self.enum_values() {
+                set_enum_schema_options(&mut obj, vals, format);
             }
-            if self.interval().has_min() {
-                obj.add_field(field("minimum", self.interval().min(0.0)));
+            set_number_schema_options(&mut obj, self, format);
+            if self.is_array() {
+                let mut arr = ObjectType::new();
+                set_common_schema_options(&mut arr, opts, format);
+                arr.add_field(field("type", "array"));
+                arr.add_field(field("items", obj));
+                value(arr)
+            } else {
+                set_common_schema_options(&mut obj, opts, format);
+                value(obj)
             }
-            if self.interval().has_max() {
-                obj.add_field(field("maximum", self.interval().max(0.0)));
+        }
+    }
+
+    // durations and sizes are strings like "1h30m" or integers in the smallest unit
+    impl<T> SchemaToValues for NumberType<T> where T: Numeric + UnitValue + format::ValueToString {
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element {
+            let mut obj = ObjectType::new();
+            match format.units() {
+                UnitFormat::String => {
+                    let as_string = |v: &T| v.convert();
+                    obj.add_field(field("type", "string"));
+                    obj.add_field(field("pattern", T::pattern()));
+                    if let Some(vals) = self.enum_values() {
+                        set_enum_schema_options(&mut obj, &vals.map(as_string), format);
+                    }
+                    set_string_interval_options(&mut obj, self.interval(), as_string);
+                },
+                UnitFormat::Integer => {
+                    let as_integer = |v: &T| v.units() as i64;
+                    obj.add_field(field("type", "integer"));
+                    obj.add_field(field("x-unit", T::unit_name()));
+                    if let Some(vals) = self.enum_values() {
+                        set_enum_schema_options(&mut obj, &vals.map(as_integer), format);
+                    }
+                    let (min, max) = interval_bounds(self.interval(), as_integer);
+                    set_bound_options(&mut obj, min, max, format);
+                    if let Some(step) = self.step() {
+                        obj.add_field(field("multipleOf", as_integer(&step)));
+                    }
+                },
             }
             if self.is_array() {
                 let mut arr = ObjectType::new();
-                set_common_schema_options(&mut arr, opts);
+                set_common_schema_options(&mut arr, opts, format);
                 arr.add_field(field("type", "array"));
                 arr.add_field(field("items", obj));
-                return value(arr);
+                value(arr)
             } else {
-                set_common_schema_options(&mut obj, opts);
-                return value(obj);
+                set_common_schema_options(&mut obj, opts, format);
+                value(obj)
+            }
+        }
+    }
+
+    // dates, times and versions are strings checked by a format or a pattern: ("format", "date")
+    fn text_schema<T>(number: &NumberType<T>, check: (&str, &str), opts: &Options, format: &Formatting) -> Element 
+        where T: Numeric + format::ValueToString {
+        let as_string = |v: &T| v.convert();
+        let mut obj = ObjectType::new();
+        obj.add_field(field("type", "string"));
+        obj.add_field(field(check.0, check.1));
+        if let Some(vals) = number.enum_values() {
+            set_enum_schema_options(&mut obj, &vals.map(as_string), format);
+        }
+        set_string_interval_options(&mut obj, number.interval(), as_string);
+        if number.is_array() {
+            let mut arr = ObjectType::new();
+            set_common_schema_options(&mut arr, opts, format);
+            arr.add_field(field("type", "array"));
+            arr.add_field(field("items", obj));
+            value(arr)
+        } else {
+            set_common_schema_options(&mut obj, opts, format);
+            value(obj)
+        }
+    }
+
+    impl SchemaToValues for SemverType {
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element {
+            text_schema(self, ("pattern", SEMVER_PATTERN), opts, format)
+        }
+    }
+
+    impl SchemaToValues for DateType {
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element {
+            text_schema(self, ("format", "date"), opts, format)
+        }
+    }
+
+    impl SchemaToValues for TimeType {
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element {
+            text_schema(self, ("pattern", TIME_PATTERN), opts, format)
+        }
+    }
+
+    impl SchemaToValues for DateTimeType {
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element {
+            text_schema(self, ("format", "date-time"), opts, format)
+        }
+    }
+
+    // ip is either ipv4 or ipv6, cidr and endpoint have no standard format
+    impl SchemaToValues for NetType {
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element {
+            let mut obj = ObjectType::new();
+            obj.add_field(field("type", "string"));
+            match self.kind() {
+                NetKind::Ip => {
+                    let mut any_of = ObjectType::new();
+                    any_of.make_array();
+                    for name in ["ipv4", "ipv6"] {
+                        let mut item = ObjectType::new();
+                        item.add_field(field("format", name));
+                        any_of.add_value(item);
+                    }
+                    obj.add_field(field("anyOf", any_of));
+                },
+                NetKind::Ipv4 => obj.add_field(field("format", "ipv4")),
+                NetKind::Ipv6 => obj.add_field(field("format", "ipv6")),
+                NetKind::Cidr | NetKind::Endpoint => (),
+            }
+            if let Some(pattern) = self.kind().pattern() {
+                obj.add_field(field("pattern", pattern));
+            }
+            if let Some(vals) = self.enum_values() {
+                set_enum_schema_options(&mut obj, vals, format);
+            }
+            if !self.networks().is_empty() {
+                let networks = self.networks().iter().map(|n| n.to_string()).collect::<Vec<String>>();
+                obj.add_field(field("x-within", &networks));
+            }
+            if self.is_array() {
+                let mut arr = ObjectType::new();
+                set_common_schema_options(&mut arr, opts, format);
+                arr.add_field(field("type", "array"));
+                arr.add_field(field("items", obj));
+                value(arr)
+            } else {
+                set_common_schema_options(&mut obj, opts, format);
+                value(obj)
+            }
+        }
+    }
+
+    // the length of the decoded data has no keyword in json schema
+    impl SchemaToValues for BytesType {
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element {
+            let mut obj = ObjectType::new();
+            obj.add_field(field("type", "string"));
+            obj.add_field(field("contentEncoding", self.encoding().content_encoding()));
+            let media_type = opts.get_string("media_type").unwrap_or("application/octet-stream");
+            obj.add_field(field("contentMediaType", media_type));
+            let length = self.length();
+            if length.has_min() {
+                let min = length.min(0) + if length.is_min_exclusive() { 1 } else { 0 };
+                obj.add_field(field("x-minBytes", min));
+            }
+            if length.has_max() {
+                let max = length.max(0) - if length.is_max_exclusive() { 1 } else { 0 };
+                obj.add_field(field("x-maxBytes", max));
+            }
+            if self.is_array() {
+                let mut arr = ObjectType::new();
+                set_common_schema_options(&mut arr, opts, format);
+                arr.add_field(field("type", "array"));
+                arr.add_field(field("items", obj));
+                value(arr)
+            } else {
+                set_common_schema_options(&mut obj, opts, format);
+                value(obj)
+            }
+        }
+    }
+
+    // unix style pattern: absolute paths start with '/'. Special characters of the extension go to []
+    fn path_pattern(path: &PathType) -> Option<String> {
+        let begin = if path.is_absolute() { "^/" } else if path.is_relative() { "^[^/]" } else { "" };
+        let end = match path.extension() {
+            Some(ext) => ext.chars().map(|c| {
+                if c.is_ascii_alphanumeric() { c.to_string() } else { format!("[{}]", c) }
+            }).collect::<String>() + "$",
+            None => String::new(),
+        };
+        if begin.is_empty() && end.is_empty() {
+            None
+        } else {
+            Some(format!("{}{}{}", begin, if begin.is_empty() || end.is_empty() { "" } else { ".*" }, end))
+        }
+    }
+
+    impl SchemaToValues for PathType {
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element {
+            let mut obj = ObjectType::new();
+            obj.add_field(field("type", "string"));
+            if let Some(pattern) = path_pattern(self) {
+                obj.add_field(field("pattern", pattern));
+            }
+            let mut rules = ObjectType::new();
+            if self.is_absolute() {
+                rules.add_field(field("absolute", true));
+            }
+            if self.is_relative() {
+                rules.add_field(field("relative", true));
+            }
+            if let Some(ext) = self.extension() {
+                rules.add_field(field("extension", ext));
+            }
+            if self.must_exist() {
+                rules.add_field(field("mustExist", true));
+            }
+            obj.add_field(field("x-path", rules));
+            if self.is_array() {
+                let mut arr = ObjectType::new();
+                set_common_schema_options(&mut arr, opts, format);
+                arr.add_field(field("type", "array"));
+                arr.add_field(field("items", obj));
+                value(arr)
+            } else {
+                set_common_schema_options(&mut obj, opts, format);
+                value(obj)
             }
         }
     }
 
     impl SchemaToValues for StringType {
-        fn value_to_schema(&self, opts: &Options) -> Element {
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element {
             let mut obj = ObjectType::new();
             obj.add_field(field("type", "string"));
 
-            match self.enum_values() {
-                Some(vals) => obj.add_field(field("enum", vals.values())),
-                None => {},
+            if let Some(vals) = self.enum_values() {
+                set_enum_schema_options(&mut obj, vals, format);
             }
 
             if self.is_array() {
                 let mut arr = ObjectType::new();
-                set_common_schema_options(&mut obj, opts);
+                set_common_schema_options(&mut arr, opts, format);
                 arr.add_field(field("type", "array"));
                 arr.add_field(field("items", obj));
-                return value(arr);
+                value(arr)
             } else {
-                set_common_schema_options(&mut obj, opts);
-                return value(obj);
+                set_common_schema_options(&mut obj, opts, format);
+                value(obj)
             }
         }
     }
 
     impl SchemaToValues for ObjectType {
-        fn value_to_schema(&self, opts: &Options) -> Element {
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element {
             let mut obj = ObjectType::new();
             obj.add_field(field("type", "object"));
             
             let mut props = ObjectType::new();
-            for (k, v) in self.fields() {
-                props.add_field(field(k, to_json_schema_impl(v.value(), v.options())))
+            for (k, v) in format.ordered(self.fields()) {
+                props.add_field(field(k, field_to_schema(v, format)))
             }
-            let required = self.fields().iter()
+            let required = format.ordered(self.fields()).into_iter()
                 .filter(|(_, v)| v.options().has_bool("required") )
                 .map(|(k, _)| k.to_string() )
                 .collect::<Vec<String>>();
-            if required.len() > 0 {
+            if !required.is_empty() {
                 obj.add_field(field("required", value(&required)));
             }
 
             obj.add_field(field("properties", props));
+            if let Some(vals) = self.enum_values() {
+                set_enum_schema_options(&mut obj, vals, format);
+            }
             if self.is_array() {
                 let mut arr = ObjectType::new();
-                set_common_schema_options(&mut arr, opts);
+                set_common_schema_options(&mut arr, opts, format);
                 arr.add_field(field("type", "array"));
                 arr.add_field(field("items", obj));
-                return value(arr);
+                value(arr)
             } else {
-                set_common_schema_options(&mut obj, opts);
-                return value(obj);
+                set_common_schema_options(&mut obj, opts, format);
+                value(obj)
             }
         }
     }
 
     impl SchemaToValues for AnyType {
-        fn value_to_schema(&self, opts: &Options) -> Element {
+        fn value_to_schema(&self, opts: &Options, format: &Formatting) -> Element {
+            if let Some(vals) = self.enum_values() {
+                let mut obj = ObjectType::new();
+                set_enum_schema_options(&mut obj, vals, format);
+                set_common_schema_options(&mut obj, opts, format);
+                return value(obj);
+            }
             match self.value() {
                 PossibleArray::Value(opt_val) => {
                     match &**opt_val {
-                        Some(val) => to_json_schema_impl(val, opts),
+                        Some(val) => to_json_schema_impl(val, opts, format),
                         None => {
                             let mut obj = ObjectType::new();
                             //obj.add_field(field("type", &vec!("object", "null")));
-                            set_common_schema_options(&mut obj, opts);
+                            set_common_schema_options(&mut obj, opts, format);
                             value(obj)
                         },
                     }
                 }
                 PossibleArray::Array(_) => {
                     let mut obj = ObjectType::new();
-                    set_common_schema_options(&mut obj, opts);
+                    set_common_schema_options(&mut obj, opts, format);
                     obj.add_field(field("type", "array"));
                     value(obj)
                 },
@@ -269,38 +720,141 @@ mod to_json_schema {
         }
     }
 
-    pub fn to_json_schema_impl<T: to_json_schema::SchemaToValues>(val: &T, opts: &Options) -> Element {
-        val.value_to_schema(opts)
+    // fields of an alias type refer to its definition
+    fn field_to_schema(fld: &FieldType, format: &Formatting) -> Element {
+        match fld.alias() {
+            Some(name) => {
+                let mut reference = ObjectType::new();
+                reference.add_field(field("$ref", format!("#/{}/{}", format.draft().definitions_keyword(), pointer_token(name))));
+                let mut opts = ObjectType::new();
+                set_common_schema_options(&mut opts, fld.options(), format);
+                if opts.fields().iter().next().is_none() {
+                    return value(reference);
+                }
+                // the older drafts need the reference in `allOf` to keep the other keywords
+                let mut obj = if format.draft().has_ref_siblings() {
+                    reference
+                } else {
+                    let mut obj = ObjectType::new();
+                    obj.add_field(field("allOf", &vec![reference]));
+                    obj
+                };
+                for (_, f) in opts.fields().iter() {
+                    obj.add_field(f.clone());
+                }
+                value(obj)
+            },
+            None => to_json_schema_impl(fld.value(), fld.options(), format),
+        }
+    }
+
+    pub fn to_json_schema_impl<T: to_json_schema::SchemaToValues>(val: &T, opts: &Options, format: &Formatting) -> Element {
+        val.value_to_schema(opts, format)
     }
 }
 
-pub fn to_json_values(val: &Module, shift: usize) -> String {
-    array_format(val.fields(), shift)
+pub fn to_json_values(val: &Module, format: &Formatting) -> String {
+    array_format(val.fields(), format)
 }
 
-pub fn to_json_schema(val: &Module, shift: usize) -> String {
+pub fn to_json_schema(val: &Module, format: &Formatting) -> String {
     let mut obj_arrays = ObjectType::new();
-    for f in val.fields() {
+    for f in format.ordered_fields(val.fields()) {
         obj_arrays.add_field(f.clone())
     }
-    element_format(&to_json_schema::to_json_schema_impl(&obj_arrays, &Options::new()), shift)
+    let schema = to_json_schema::to_json_schema_impl(&obj_arrays, &Options::new(), format);
+    schema_document(val, val.name(), schema, format)
+}
+
+// a step of durations and sizes can't be checked on a string, it is only in the schema with `--units=integer`
+fn dropped_steps(fld: &FieldType, path: &str, format: &Formatting, warnings: &mut Vec<String>) {
+    let at = format!("{}/{}", path, pointer_token(fld.name()));
+    let step = match fld.value() {
+        Element::Duration(v) => v.step().map(|s| s.to_string()),
+        Element::Size(v) => v.step().map(|s| s.to_string()),
+        Element::Object(obj) => {
+            for (_, f) in obj.fields().iter() {
+                dropped_steps(f, &at, format, warnings);
+            }
+            None
+        },
+        _ => None,
+    };
+    if let (Some(step), UnitFormat::String) = (step, format.units()) {
+        warnings.push(format!("{}: the step {} is not written with --units=string", at, step));
+    }
+}
+
+pub fn json_schema_warnings(fields: &[FieldType], format: &Formatting) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for fld in fields {
+        dropped_steps(fld, "", format, &mut warnings);
+    }
+    warnings
+}
+
+// the schema of one top-level field, its id is the module name with the field name
+pub fn field_to_json_schema(module: &Module, val: &FieldType, format: &Formatting) -> String {
+    let id = if !module.name().is_empty() { format!("{}.{}", module.name(), val.name()) } else { String::new() };
+    schema_document(module, &id, field_schema(val, format), format)
+}
+
+// an omitted secret root is `null`
+pub fn field_to_json_values(val: &FieldType, format: &Formatting) -> String {
+    format::field_value_format(val, format, 0).unwrap_or_else(|| "null".to_string())
+}
+
+fn schema_document(module: &Module, id: &str, schema: Element, format: &Formatting) -> String {
+    let draft = format.draft();
+    let mut root = ObjectType::new();
+    root.add_field(text_field("$schema", draft.uri()));
+    if !id.is_empty() {
+        root.add_field(text_field(draft.id_keyword(), &format!("urn:schemer:{}", id)));
+    }
+    if let Element::Object(schema) = schema {
+        for (_, f) in schema.fields().iter() {
+            root.add_field(f.clone());
+        }
+    }
+    // aliases are reusable definitions
+    if !module.aliases().is_empty() {
+        let mut defs = ObjectType::new();
+        for f in format.ordered_fields(module.aliases()) {
+            let schema = to_json_schema::to_json_schema_impl(f.value(), f.options(), format);
+            defs.add_field(FieldType::new(f.name().to_string(), schema, Options::new()));
+        }
+        root.add_field(FieldType::new(draft.definitions_keyword().to_string(), Element::Object(defs), Options::new()));
+    }
+    element_format(&Element::Object(root), format)
 }
 
-fn _field_to_json_values(val: &FieldType, shift: usize) -> String {
-    element_format(val.value(), shift)
+fn text_field(name: &str, val: &str) -> FieldType {
+    FieldType::new(name.to_string(), Element::String(StringType::from(val)), Options::new())
 }
 
-fn _field_to_json_schema(val: &FieldType, shift: usize) -> String {
+fn field_schema(val: &FieldType, format: &Formatting) -> Element {
     use to_json_schema::to_json_schema_impl as call_impl;
     let schema_obj = match val.value() {
-        Element::Boolean(v) => { call_impl(v, val.options()) },
-        Element::String(v) => { call_impl(v, val.options()) },
-        Element::Integer(v) => { call_impl(v, val.options()) },
-        Element::Floating(v) => { call_impl(v, val.options()) },
-        Element::Object(v) => { call_impl(v, val.options()) },
-        Element::Any(v) => { call_impl(v, val.options()) },
+        Element::Boolean(v) => { call_impl(v, val.options(), format) },
+        Element::String(v) => { call_impl(v, val.options(), format) },
+        Element::Integer(v) => { call_impl(v, val.options(), format) },
+        Element::Unsigned(v) => { call_impl(v, val.options(), format) },
+        Element::BigInt(v) => { call_impl(v, val.options(), format) },
+        Element::Floating(v) => { call_impl(v, val.options(), format) },
+        Element::Decimal(v) => { call_impl(v, val.options(), format) },
+        Element::Duration(v) => { call_impl(v, val.options(), format) },
+        Element::Size(v) => { call_impl(v, val.options(), format) },
+        Element::Date(v) => { call_impl(v, val.options(), format) },
+        Element::Time(v) => { call_impl(v, val.options(), format) },
+        Element::DateTime(v) => { call_impl(v, val.options(), format) },
+        Element::Net(v) => { call_impl(v, val.options(), format) },
+        Element::Semver(v) => { call_impl(v, val.options(), format) },
+        Element::Bytes(v) => { call_impl(v, val.options(), format) },
+        Element::Path(v) => { call_impl(v, val.options(), format) },
+        Element::Object(v) => { call_impl(v, val.options(), format) },
+        Element::Any(v) => { call_impl(v, val.options(), format) },
         //Element::None => "".to_string(),
         _ => Element::None,
     };
-    element_format(&schema_obj, shift)
+    schema_obj
 }
@@ -5,13 +5,14 @@ use super::helpers::*;
 use super::formatting::*;
 
 mod utils {
+    // an identifier can't be empty or start with a digit
     pub fn is_ident_string(val: &str) -> bool {
-        val.chars().find(|c| {
-            !(c.is_ascii_alphabetic() || c.is_digit(10) || *c == '_') 
+        !val.starts_with(|c: char| c.is_ascii_digit()) && !val.is_empty() && val.chars().find(|c| {
+            !(c.is_ascii_alphabetic() || c.is_ascii_digit() || *c == '_') 
         }).is_none()
     }
     pub fn quote(val: &str) -> String {
-        return if !is_ident_string(val) { format!("\"{}\"", val) } else { val.to_string() }
+        if !is_ident_string(val) { super::schemer_quote(val) } else { val.to_string() }
     }
 }
 
@@ -24,6 +25,17 @@ fn cast<T: ToSchemerString>(val: &T) -> &T {
     val
 }
 
+fn enum_to_string<T, F>(values: &Enum<T>, format: &Formatting, shift: usize, value_to: F) -> String 
+    where T: PartialEq + Clone, F: Fn(&T) -> String {
+    let vals = values.values().iter().enumerate().map(|(id, v)| {
+        match values.label(id) {
+            Some(label) => format!("{}: {}", value_to(v), format.format_value(&label.to_string())),
+            None => value_to(v),
+        }
+    }).collect::<Vec<String>>();
+    format!(" enum {{{}}}", format.format_array(&vals, shift))
+}
+
 impl ToSchemerString for BooleanType {
     fn field_to(&self, _: &Formatting, _: usize) -> String {
         format!("boolean{}", 
@@ -46,9 +58,7 @@ impl ToSchemerString for StringType {
     fn field_to(&self, format: &Formatting, shift: usize) -> String {
         let opt_enum = self.enum_values();
         let enum_string = match &opt_enum {
-            Some(values) => {
-                format!(" enum {{{}}}", format.format_t_array(values.values(), shift))
-            },
+            Some(values) => enum_to_string(values, format, shift, |v| format.format_value(v)),
             None => String::new(),
         };
         format!("string{}{}", 
@@ -59,7 +69,77 @@ impl ToSchemerString for StringType {
     fn value_to(&self, format: &Formatting, shift: usize) -> String {
         match self.value() {
             PossibleArray::Array(arr) => {
-                format!("[{}]", format.format_t_array(&arr, shift + 1))
+                format!("[{}]", format.format_t_array(arr, shift + 1))
+            },
+            PossibleArray::Value(val) => {
+                format.format_value(val)
+            },
+        }
+    }
+}
+
+impl ToSchemerString for BytesType {
+    fn field_to(&self, format: &Formatting, _: usize) -> String {
+        let ival = self.length();
+        let interval = if ival.has_minmax() { 
+            format!(" {}{}..{}{}", 
+                if ival.has_min() { format.format_value(&ival.min(0)) } else { String::new() }, 
+                if ival.is_min_exclusive() { "<" } else { "" },
+                if ival.is_max_exclusive() { "<" } else { "" },
+                if ival.has_max() { format.format_value(&ival.max(0)) } else { String::new() } )
+        } else {
+            String::new()
+        };
+        format!("bytes{}{}", if self.is_array() { "[]" } else { "" }, interval)
+    }
+    fn value_to(&self, format: &Formatting, shift: usize) -> String {
+        match self.value() {
+            PossibleArray::Array(arr) => {
+                format!("[{}]", format.format_t_array(arr, shift + 1))
+            },
+            PossibleArray::Value(val) => {
+                format.format_value(val)
+            },
+        }
+    }
+}
+
+// the rules of the path are in the field options
+impl ToSchemerString for PathType {
+    fn field_to(&self, _: &Formatting, _: usize) -> String {
+        format!("path{}", if self.is_array() { "[]" } else { "" })
+    }
+    fn value_to(&self, format: &Formatting, shift: usize) -> String {
+        match self.value() {
+            PossibleArray::Array(arr) => {
+                format!("[{}]", format.format_t_array(arr, shift + 1))
+            },
+            PossibleArray::Value(val) => {
+                format.format_value(val)
+            },
+        }
+    }
+}
+
+impl ToSchemerString for NetType {
+    fn field_to(&self, format: &Formatting, shift: usize) -> String {
+        let within = self.networks().iter().map(|n| {
+            format!(" within {}", format.format_value(&n.to_string()))
+        }).collect::<String>();
+        let enum_string = match self.enum_values() {
+            Some(values) => enum_to_string(values, format, shift, |v| format.format_value(v)),
+            None => String::new(),
+        };
+        format!("{}{}{}{}", self.kind().name(),
+            if self.is_array() { "[]" } else { "" },
+            within,
+            enum_string
+        )
+    }
+    fn value_to(&self, format: &Formatting, shift: usize) -> String {
+        match self.value() {
+            PossibleArray::Array(arr) => {
+                format!("[{}]", format.format_t_array(arr, shift + 1))
             },
             PossibleArray::Value(val) => {
                 format.format_value(val)
@@ -72,23 +152,27 @@ impl<T> ToSchemerString for NumberType<T> where T: Numeric + Clone + format::Val
     fn field_to(&self, format: &Formatting, shift: usize) -> String {
         let ival = self.interval();
         let interval = if ival.has_minmax() { 
-            format!(" {}..{}", 
+            format!(" {}{}..{}{}", 
                 if ival.has_min() { format.format_value(&ival.min(T::zero())) } else { String::new() }, 
-                if ival.has_min() { format.format_value(&ival.max(T::zero())) } else { String::new() } )
+                if ival.is_min_exclusive() { "<" } else { "" },
+                if ival.is_max_exclusive() { "<" } else { "" },
+                if ival.has_max() { format.format_value(&ival.max(T::zero())) } else { String::new() } )
         } else {
             String::new()
         };
+        let step = match self.step() {
+            Some(val) => format!(" step {}", format.format_value(&val)),
+            None => String::new(),
+        };
         let opt_enum = self.enum_values();
         let enum_string = match &opt_enum {
-            Some(values) => {
-                format!(" enum {{{}}}", 
-                    format.format_t_array(values.values(), shift))
-            },
+            Some(values) => enum_to_string(values, format, shift, |v| format.format_value(v)),
             None => String::new(),
         };
-        format!("{}{}{}{}", T::name(), 
+        format!("{}{}{}{}{}", T::name(), 
             if self.is_array() { "[]" } else { "" }, 
             interval, 
+            step,
             enum_string
         )
     }
@@ -99,7 +183,7 @@ impl<T> ToSchemerString for NumberType<T> where T: Numeric + Clone + format::Val
                 format!("[{}]", format.format_t_array(arr, shift + 1))
             },
             PossibleArray::Value(val) => {
-                val.to_string()
+                format.format_value(val)
             },
         }
     }
@@ -109,14 +193,20 @@ impl ToSchemerString for ObjectType {
     fn field_to(&self, format: &Formatting, shift: usize) -> String {
 
         let fields = format.format_array(
-            &self.fields().iter().map(|(_, f)| {
+            &format.ordered(self.fields()).into_iter().map(|(_, f)| {
                 field_to_string_impl(f, format, shift + 1)
             }).collect::<Vec<String>>(), 
         shift);
 
-        format!("object{} {{{}}}", 
+        let enum_string = match self.enum_values() {
+            Some(values) => enum_to_string(values, format, shift, |v| cast(v).value_to(format, shift + 1)),
+            None => String::new(),
+        };
+
+        format!("object{} {{{}}}{}", 
             if self.is_array() { "[]" } else { "" },
             fields,
+            enum_string,
         )
     }
     fn value_to(&self, format: &Formatting, shift: usize) -> String {
@@ -137,7 +227,7 @@ impl ToSchemerString for ObjectType {
                     },
                     None => self.fields(),
                 };
-                let str_value = fields.iter().map(|(_, v)| {
+                let str_value = format.ordered(fields).into_iter().map(|(_, v)| {
                     values_to_string(v, format, shift + 1)
                 }).collect::<Vec<String>>();
                 format!("{{{}}}", format.format_array(&str_value, shift))
@@ -147,8 +237,12 @@ impl ToSchemerString for ObjectType {
 }
 
 impl ToSchemerString for AnyType {
-    fn field_to(&self, _: &Formatting, _: usize) -> String {
-        "any".to_string()
+    fn field_to(&self, format: &Formatting, shift: usize) -> String {
+        match self.enum_values() {
+            Some(values) => format!("any{}", 
+                enum_to_string(values, format, shift, |v| cast(v).value_to(format, shift + 1))),
+            None => "any".to_string(),
+        }
     }
     fn value_to(&self, format: &Formatting, shift: usize) -> String {
         match self.value() {
@@ -179,7 +273,19 @@ impl ToSchemerString for Element {
             Element::Boolean(v) => { cast(v).field_to(format, shift) },
             Element::String(v) => { cast(v).field_to(format, shift) },
             Element::Integer(v) => { cast(v).field_to(format, shift) },
+            Element::Unsigned(v) => { cast(v).field_to(format, shift) },
+            Element::BigInt(v) => { cast(v).field_to(format, shift) },
             Element::Floating(v) => { cast(v).field_to(format, shift) },
+            Element::Decimal(v) => { cast(v).field_to(format, shift) },
+            Element::Duration(v) => { cast(v).field_to(format, shift) },
+            Element::Size(v) => { cast(v).field_to(format, shift) },
+            Element::Path(v) => { cast(v).field_to(format, shift) },
+            Element::Bytes(v) => { cast(v).field_to(format, shift) },
+            Element::Semver(v) => { cast(v).field_to(format, shift) },
+            Element::Net(v) => { cast(v).field_to(format, shift) },
+            Element::Date(v) => { cast(v).field_to(format, shift) },
+            Element::Time(v) => { cast(v).field_to(format, shift) },
+            Element::DateTime(v) => { cast(v).field_to(format, shift) },
             Element::Object(v) => { cast(v).field_to(format, shift) },
             Element::Any(v) => { cast(v).field_to(format, shift) },
             Element::None => "".to_string(),
@@ -190,7 +296,19 @@ impl ToSchemerString for Element {
             Element::Boolean(v) => { cast(v).value_to(format, shift) },
             Element::String(v) => { cast(v).value_to(format, shift) },
             Element::Integer(v) => { cast(v).value_to(format, shift) },
+            Element::Unsigned(v) => { cast(v).value_to(format, shift) },
+            Element::BigInt(v) => { cast(v).value_to(format, shift) },
             Element::Floating(v) => { cast(v).value_to(format, shift) },
+            Element::Decimal(v) => { cast(v).value_to(format, shift) },
+            Element::Duration(v) => { cast(v).value_to(format, shift) },
+            Element::Size(v) => { cast(v).value_to(format, shift) },
+            Element::Path(v) => { cast(v).value_to(format, shift) },
+            Element::Bytes(v) => { cast(v).value_to(format, shift) },
+            Element::Semver(v) => { cast(v).value_to(format, shift) },
+            Element::Net(v) => { cast(v).value_to(format, shift) },
+            Element::Date(v) => { cast(v).value_to(format, shift) },
+            Element::Time(v) => { cast(v).value_to(format, shift) },
+            Element::DateTime(v) => { cast(v).value_to(format, shift) },
             Element::Object(v) => { cast(v).value_to(format, shift) },
             Element::Any(v) => { cast(v).value_to(format, shift) },
             Element::None => "null".to_string(),
@@ -200,7 +318,7 @@ impl ToSchemerString for Element {
 
 fn field_values_to_string<T: ObjectBase + ToSchemerString>(val: &T, format: &Formatting, shift: usize, ignore_default: bool) -> String {
     if val.is_default() && !ignore_default {
-        format!("{}", val.field_to(format, shift))
+        val.field_to(format, shift).to_string()
     } else {
         format!("{} = {}", val.field_to(format, shift), val.value_to(format, shift))
     }
@@ -210,7 +328,7 @@ fn options_to_string(opts: &Options, format: &Formatting, shift: usize) -> Strin
     if opts.empty() {
         String::new()
     } else {
-        let vals = opts.all().iter().map(|(k, v)|{
+        let vals = format.ordered(opts.all()).into_iter().map(|(k, v)|{
             format!("{}: {}", k, cast(v).value_to(format, shift + 1))
         }).collect::<Vec<String>>();
         format!("({})", format.format_array(&vals, shift + 1))
@@ -220,12 +338,24 @@ fn options_to_string(opts: &Options, format: &Formatting, shift: usize) -> Strin
 fn field_to_string_impl(val: &FieldType, format: &Formatting, shift: usize) -> String {
     format!("{}{}: {}", 
         &utils::quote(val.name()), 
-        &options_to_string(&val.options(), format, shift),
+        &options_to_string(val.options(), format, shift),
         match val.value() {
             Element::Boolean(v) => { field_values_to_string(v, format, shift, false) },
             Element::String(v) => { field_values_to_string(v, format, shift, false) },
             Element::Integer(v) => { field_values_to_string(v, format, shift, false) },
+            Element::Unsigned(v) => { field_values_to_string(v, format, shift, false) },
+            Element::BigInt(v) => { field_values_to_string(v, format, shift, false) },
             Element::Floating(v) => { field_values_to_string(v, format, shift, false) },
+            Element::Decimal(v) => { field_values_to_string(v, format, shift, false) },
+            Element::Duration(v) => { field_values_to_string(v, format, shift, false) },
+            Element::Size(v) => { field_values_to_string(v, format, shift, false) },
+            Element::Path(v) => { field_values_to_string(v, format, shift, false) },
+            Element::Bytes(v) => { field_values_to_string(v, format, shift, false) },
+            Element::Semver(v) => { field_values_to_string(v, format, shift, false) },
+            Element::Net(v) => { field_values_to_string(v, format, shift, false) },
+            Element::Date(v) => { field_values_to_string(v, format, shift, false) },
+            Element::Time(v) => { field_values_to_string(v, format, shift, false) },
+            Element::DateTime(v) => { field_values_to_string(v, format, shift, false) },
             Element::Object(v) => { field_values_to_string(v, format, shift, false) },
             Element::Any(v) => { field_values_to_string(v, format, shift, false) },
             Element::None => "".to_string(),
@@ -245,16 +375,32 @@ fn values_to_string(val: &FieldType, format: &Formatting, shift: usize) -> Strin
 //     field_to_string_impl(val, &format, 0)
 // }
 
-pub fn module_to_string(val: &Module, shift: usize) -> String {
-    let format = Formatting::new(shift);
+pub fn module_to_string(val: &Module, format: &Formatting) -> String {
     let mut res = String::new();
-    if val.name().len() > 0 {
-        res = val.name().to_string();
-        res.push_str("\n");
+    if !val.name().is_empty() {
+        res = format!("mod {}\n", val.name());
     }
-    for v in val.fields() {
-        res.push_str(&field_to_string_impl(v, &format, 0));
-        res.push_str("\n");
+    // aliases keep their order, the later ones can use the earlier ones
+    for v in val.aliases() {
+        res.push_str("alias ");
+        res.push_str(&field_to_string_impl(v, format, 0));
+        res.push('\n');
+    }
+    for v in format.ordered_fields(val.fields()) {
+        res.push_str(&field_to_string_impl(v, format, 0));
+        res.push('\n');
     } 
-    return res;
+    // profiles keep their order, parents are defined before
+    for p in val.profiles() {
+        let values = p.values().iter().map(|(path, fld)| {
+            let path = path.iter().map(|v| utils::quote(v)).collect::<Vec<String>>().join(".");
+            format!("{} = {}", path, cast(fld.value()).value_to(format, 1))
+        }).collect::<Vec<String>>();
+        res.push_str(&format!("profile {}", utils::quote(p.name())));
+        if let Some(parent) = p.parent() {
+            res.push_str(&format!(": {}", utils::quote(parent)));
+        }
+        res.push_str(&format!(" {{{}}}\n", format.format_array(&values, 0)));
+    }
+    res
 }
@@ -1,3 +1,10 @@
+use super::helpers::{Duration, ByteSize};
+use super::datetime::{Date, Time, DateTime};
+use super::semver::Version;
+use super::bigint::BigInt;
+use super::decimal::Float;
+use super::formatting::schemer_quote;
+
 
 #[derive(Clone, PartialEq)]
 pub enum SpecialToken {
@@ -24,8 +31,13 @@ pub enum SpecialToken {
     Hash, // #
 
     HexBegin, // 0x, 0X
+    OctBegin, // 0o, 0O
+    BinBegin, // 0b, 0B
 
     Interval, // ..
+    Less, // <
+    Step, // step
+    Within, // within
 
     Mod, // mod
     Alias, // alias
@@ -35,10 +47,26 @@ pub enum SpecialToken {
 pub enum TypeName {
     TypeString, // string
     TypeInteger, // interger
+    TypeUnsigned, // unsigned, u64
+    TypeBigInt, // bigint
     TypeFloating, // floating
+    TypeDecimal, // decimal
     TypeBoolean, // boolean
     TypeObject, // object
     TypeAny, // any
+    TypeDuration, // duration
+    TypeSize, // size
+    TypeDate, // date
+    TypeTime, // time
+    TypeDateTime, // datetime
+    TypeIp, // ip
+    TypeIpv4, // ipv4
+    TypeIpv6, // ipv6
+    TypeCidr, // cidr
+    TypeEndpoint, // endpoint
+    TypeSemver, // semver
+    TypeBytes, // bytes
+    TypePath, // path
 }
 
 #[derive(Clone, PartialEq)]
@@ -46,7 +74,14 @@ pub enum Token {
     None, 
     Ident(String),
     Integer(i64),
-    Floating(f64),
+    BigInteger(BigInt), // integers that don't fit i64
+    Floating(Float), // keeps the literal
+    Duration(u64), // 1h30m, milliseconds
+    Size(u64, u64), // 64KiB, bytes and the multiplier of the unit
+    Date(Date), // 2020-01-01
+    Time(Time), // 12:30:00
+    DateTime(DateTime), // 2020-01-01T12:30:00Z
+    Version(Version), // 1.2.0-beta.1
     String(String),
     Boolean(bool),
     Type(TypeName),
@@ -57,48 +92,26 @@ pub enum Token {
 impl Token { 
     #![allow(unused)]
     pub fn is_ident(&self) -> bool {
-        match self {
-            Token::Ident(_) => true,
-            _ => false
-        }
+        matches!(self, Token::Ident(_))
     }
     pub fn is_floating(&self) -> bool {
-        match self {
-            Token::Floating(_) => true,
-            _ => false
-        }
+        matches!(self, Token::Floating(_))
     }
     pub fn is_integer(&self) -> bool {
-        match self {
-            Token::Integer(_) => true,
-            _ => false
-        }
+        matches!(self, Token::Integer(_) | Token::BigInteger(_))
     }
     pub fn is_number(&self) -> bool {
-        match self {
-            Token::Integer(_) => true,
-            Token::Floating(_) => true,
-            _ => false
-        }
+        matches!(self, Token::Integer(_) | Token::BigInteger(_) | Token::Floating(_))
     }
     pub fn is_string(&self) -> bool {
-        match self {
-            Token::String(_) => true,
-            _ => false
-        }
+        matches!(self, Token::String(_))
     }
 
     pub fn is_boolean(&self) -> bool {
-        match self {
-            Token::Boolean(_) => true,
-            _ => false
-        }
+        matches!(self, Token::Boolean(_))
     }
     pub fn is_eof(&self) -> bool {
-        match self {
-            Token::Eof => true,
-            _ => false
-        }
+        matches!(self, Token::Eof)
     }
     pub fn is_special(val: SpecialToken) -> impl Fn(&Token) -> bool {
         move |tok: &Token| match tok {
@@ -109,10 +122,7 @@ impl Token {
         }
     }
     pub fn is_type(&self) -> bool {
-        match self {
-            Token::Type(_) => true,
-            _ => false
-        }
+        matches!(self, Token::Type(_))
     }
 }
 
@@ -141,28 +151,53 @@ impl TokenInfo {
     }
 
     pub fn literal(&self) -> &str {
-        return &self.literal
+        &self.literal
     }
 
     pub fn set_literal(&mut self, val: String) {
         self.literal = val;
     }
+}
 
-    pub fn to_string(&self) -> String {
-        match &self.token {
-            Token::None => format!("None"),
-            Token::Ident(ident) => format!("{}", ident),
+impl std::fmt::Display for TokenInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match &self.token {
+            Token::None => "None".to_string(),
+            Token::Ident(ident) => ident.to_string(),
             Token::Integer(i) => format!("{}", i),
+            Token::BigInteger(i) => i.to_string(),
             Token::Floating(f) => format!("{}", f),
-            Token::String(s) => format!("\"{}\"", s),
+            Token::Duration(d) => Duration::from_millis(*d).to_string(),
+            Token::Size(s, unit) => ByteSize::with_unit(*s, *unit).to_string(),
+            Token::Date(d) => d.to_string(),
+            Token::Time(t) => t.to_string(),
+            Token::DateTime(d) => d.to_string(),
+            Token::Version(v) => v.to_string(),
+            Token::String(s) => schemer_quote(s),
             Token::Boolean(b) => (if *b { "true" } else { "false" }).to_string(),
             Token::Type(t) => match t {
                 TypeName::TypeString => "string".to_string(),
                 TypeName::TypeInteger => "integer".to_string(),
+                TypeName::TypeUnsigned => "unsigned".to_string(),
+                TypeName::TypeBigInt => "bigint".to_string(),
                 TypeName::TypeFloating => "floating".to_string(),
+                TypeName::TypeDecimal => "decimal".to_string(),
                 TypeName::TypeBoolean => "boolean".to_string(),
                 TypeName::TypeObject => "object".to_string(),
                 TypeName::TypeAny => "any".to_string(),
+                TypeName::TypeDuration => "duration".to_string(),
+                TypeName::TypeSize => "size".to_string(),
+                TypeName::TypeDate => "date".to_string(),
+                TypeName::TypeTime => "time".to_string(),
+                TypeName::TypeDateTime => "datetime".to_string(),
+                TypeName::TypeIp => "ip".to_string(),
+                TypeName::TypeIpv4 => "ipv4".to_string(),
+                TypeName::TypeIpv6 => "ipv6".to_string(),
+                TypeName::TypeCidr => "cidr".to_string(),
+                TypeName::TypeEndpoint => "endpoint".to_string(),
+                TypeName::TypeSemver => "semver".to_string(),
+                TypeName::TypeBytes => "bytes".to_string(),
+                TypeName::TypePath => "path".to_string(),
             }
             Token::Special(spec) => match spec { 
                 SpecialToken::LParen => "(".to_string(),
@@ -177,17 +212,23 @@ impl TokenInfo {
                 SpecialToken::Colon => ":".to_string(),
                 SpecialToken::Semicolon => ";".to_string(),
                 SpecialToken::Interval => "..".to_string(),
+                SpecialToken::Less => "<".to_string(),
+                SpecialToken::Step => "step".to_string(),
+                SpecialToken::Within => "within".to_string(),
                 SpecialToken::Plus => "+".to_string(),
                 SpecialToken::Minus => "-".to_string(),
                 SpecialToken::Hash => "#".to_string(),
                 SpecialToken::HexBegin => "0x".to_string(),
+                SpecialToken::OctBegin => "0o".to_string(),
+                SpecialToken::BinBegin => "0b".to_string(),
                 SpecialToken::Enum => "enum".to_string(),
                 SpecialToken::Null => "null".to_string(),
                 SpecialToken::Mod => "mod".to_string(),
                 SpecialToken::Alias => "alias".to_string(),
             },
             Token::Eof => "eof".to_string(),
-        }
+        };
+        f.write_str(&text)
     }
 }
 
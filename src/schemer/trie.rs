@@ -16,7 +16,7 @@ impl<T> Trie<T> {
         }
     }
 
-    pub fn get<'a, 'b>(&'a self, data: &'b mut Scanner) -> Option<(&'a T, usize)> {
+    pub fn get<'a>(&'a self, data: &mut Scanner) -> Option<(&'a T, usize)> {
         let mut root = self;
         let mut last: Option<&T> = None;
         let mut last_shift: usize = 0;
@@ -29,7 +29,7 @@ impl<T> Trie<T> {
             let mut last_set = false;
             match next {
                 Some(expr) => {
-                    root = &expr;
+                    root = expr;
                     if root.data.is_some() {
                         last = root.value_ref();
                         last_set = true;
@@ -45,7 +45,7 @@ impl<T> Trie<T> {
         }
 
         data.restore(&other); 
-        return match last {
+        match last {
             Some(expr) => Some((expr, last_shift)),
             None => None,
         }
@@ -57,12 +57,12 @@ impl<T> Trie<T> {
             root = root.children.entry(c).or_insert(Trie::new());
         }
         root.data = Some(value);
-        return true;
+        true
     }
 
     fn value_ref(&self) -> Option<&T> {
         match &self.data {
-            Some(expr) => Some(&expr),
+            Some(expr) => Some(expr),
             None => None,
         }
     }
@@ -0,0 +1,493 @@
+use super::objects::*;
+use super::helpers::*;
+use super::json::{JsonValue, pointer_token};
+use super::lexer::Lexer;
+use super::tokens::Token;
+use super::bigint::BigInt;
+use super::decimal::{Float, Decimal};
+use super::datetime::{Date, Time, DateTime};
+use super::semver::Version;
+use super::formatting::{Formatting, SecretMode, element_format};
+
+// a place of the document that doesn't match the schema
+pub struct Violation {
+    path: String,
+    message: String,
+    // the declaration of the field with the rule
+    position: Option<(usize, usize)>,
+}
+
+impl Violation {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+    pub fn position(&self) -> Option<(usize, usize)> {
+        self.position
+    }
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = if self.path.is_empty() { "(root)" } else { &self.path };
+        write!(f, "{}: {}", path, self.message)?;
+        if let Some(pos) = self.position {
+            write!(f, " (rule at {}:{})", pos.0, pos.1)?;
+        }
+        Ok(())
+    }
+}
+
+// values of json numbers and strings
+pub trait FromJson: Sized {
+    fn from_json(val: &JsonValue) -> Option<Self>;
+}
+
+mod utils {
+    use super::*;
+
+    // a literal like 1h30m or 10MB, read by the lexer
+    pub fn unit_token(val: &str) -> Option<Token> {
+        let tokens = Lexer::new().run(val).ok()?;
+        let tokens = tokens.iter().map(|t| t.token()).filter(|t| !matches!(t, Token::None | Token::Eof)).collect::<Vec<&Token>>();
+        match tokens.as_slice() {
+            [tok] => Some((*tok).clone()),
+            _ => None,
+        }
+    }
+
+    // the interval as it is written in schemer: 1..<10
+    pub fn interval_text<T: Numeric>(val: &Interval<T>) -> String {
+        format!("{}{}..{}{}",
+            if val.has_min() { val.min(T::zero()).to_string() } else { String::new() },
+            if val.is_min_exclusive() { "<" } else { "" },
+            if val.is_max_exclusive() { "<" } else { "" },
+            if val.has_max() { val.max(T::zero()).to_string() } else { String::new() })
+    }
+
+    fn same_items<T, F>(current: &PossibleArray<T>, val: &JsonValue, same: F) -> bool
+        where T: Clone, F: Fn(&T, &JsonValue) -> bool {
+        match current {
+            PossibleArray::Value(v) => same(v, val),
+            PossibleArray::Array(items) => match val.as_array() {
+                Some(other) => items.len() == other.len() && items.iter().zip(other.iter()).all(|(a, b)| same(a, b)),
+                None => false,
+            },
+        }
+    }
+
+    fn same_number<T: Numeric + FromJson>(num: &NumberType<T>, val: &JsonValue) -> bool {
+        same_items(num.value(), val, |v, item| T::from_json(item).is_some_and(|other| other == *v))
+    }
+
+    fn same_text(current: &PossibleArray<String>, val: &JsonValue) -> bool {
+        same_items(current, val, |v, item| item.as_str() == Some(v.as_str()))
+    }
+
+    // missing keys of a typed object have the default values of the `schema` fields
+    pub fn same_object(obj: &ObjectType, val: &JsonValue, schema: Option<&ObjectType>) -> bool {
+        let fields = match val.as_object() {
+            Some(v) => v,
+            None => return false,
+        };
+        let expected = obj.value_fields();
+        fields.iter().all(|(name, _)| expected.get(name).is_some()) && expected.iter().all(|(name, fld)| {
+            let nested = schema.and_then(|s| s.get_field(name)).and_then(|f| match f.value() {
+                Element::Object(v) => Some(v),
+                _ => None,
+            });
+            match fields.get(name) {
+                Some(v) => match fld.value() {
+                    Element::Object(v_obj) if !v_obj.value().is_array() => same_object(v_obj, v, nested),
+                    other => same_value(other, v),
+                },
+                None => schema.and_then(|s| s.value_fields().get(name)).is_some_and(|f| f.value() == fld.value()),
+            }
+        })
+    }
+
+    // the json value is the value of the element, for enums of objects and `any`
+    pub fn same_value(el: &Element, val: &JsonValue) -> bool {
+        match el {
+            Element::None => false,
+            Element::Any(v) => same_items(v.value(), val, |item, json| match &**item {
+                Some(inner) => same_value(inner, json),
+                None => matches!(json, JsonValue::Null),
+            }),
+            Element::Object(v) => match v.value() {
+                PossibleArray::Array(items) => match val.as_array() {
+                    Some(other) => items.len() == other.len() && items.iter().zip(other.iter()).all(|(a, b)| match &**a {
+                        Some(obj) => same_object(obj, b, None),
+                        None => false,
+                    }),
+                    None => false,
+                },
+                PossibleArray::Value(_) => same_object(v, val, None),
+            },
+            Element::Boolean(v) => same_items(v.value(), val, |b, item| item.as_bool() == Some(*b)),
+            Element::String(v) => same_text(v.value(), val),
+            Element::Bytes(v) => same_text(v.value(), val),
+            Element::Path(v) => same_text(v.value(), val),
+            Element::Net(v) => same_items(v.value(), val, |s, item| {
+                item.as_str().and_then(|text| v.kind().normalize(text).ok()).as_ref() == Some(s)
+            }),
+            Element::Integer(v) => same_number(v, val),
+            Element::Unsigned(v) => same_number(v, val),
+            Element::BigInt(v) => same_number(v, val),
+            Element::Floating(v) => same_number(v, val),
+            Element::Decimal(v) => same_number(v, val),
+            Element::Duration(v) => same_number(v, val),
+            Element::Size(v) => same_number(v, val),
+            Element::Date(v) => same_number(v, val),
+            Element::Time(v) => same_number(v, val),
+            Element::DateTime(v) => same_number(v, val),
+            Element::Semver(v) => same_number(v, val),
+        }
+    }
+
+    pub fn shown(val: &JsonValue) -> String {
+        match val {
+            JsonValue::String(s) => super::super::formatting::json_quote(s),
+            JsonValue::Number(n) => n.clone(),
+            other => other.type_name().to_string(),
+        }
+    }
+}
+
+impl FromJson for i64 {
+    fn from_json(val: &JsonValue) -> Option<Self> {
+        val.as_i64()
+    }
+}
+
+impl FromJson for u64 {
+    fn from_json(val: &JsonValue) -> Option<Self> {
+        val.as_number()?.parse::<u64>().ok()
+    }
+}
+
+impl FromJson for BigInt {
+    fn from_json(val: &JsonValue) -> Option<Self> {
+        let lit = val.as_number()?;
+        match lit.strip_prefix('-') {
+            Some(digits) => BigInt::from_decimal(digits, true),
+            None => BigInt::from_decimal(lit, false),
+        }
+    }
+}
+
+impl FromJson for Float {
+    fn from_json(val: &JsonValue) -> Option<Self> {
+        Float::parse(val.as_number()?).ok()
+    }
+}
+
+// decimals can be strings, so json parsers don't round them
+impl FromJson for Decimal {
+    fn from_json(val: &JsonValue) -> Option<Self> {
+        match val {
+            JsonValue::Number(lit) | JsonValue::String(lit) => Decimal::parse(lit).ok(),
+            _ => None,
+        }
+    }
+}
+
+// durations and sizes are strings (1h30m) or integers in milliseconds and bytes
+impl FromJson for Duration {
+    fn from_json(val: &JsonValue) -> Option<Self> {
+        match val {
+            JsonValue::Number(_) => u64::from_json(val).map(Duration::from_millis),
+            JsonValue::String(s) => match utils::unit_token(s)? {
+                Token::Duration(v) => Some(Duration::from_millis(v)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl FromJson for ByteSize {
+    fn from_json(val: &JsonValue) -> Option<Self> {
+        match val {
+            JsonValue::Number(_) => u64::from_json(val).map(ByteSize::from_bytes),
+            JsonValue::String(s) => match utils::unit_token(s)? {
+                Token::Size(v, unit) => Some(ByteSize::with_unit(v, unit)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl FromJson for Date {
+    fn from_json(val: &JsonValue) -> Option<Self> {
+        Date::parse(val.as_str()?).ok()
+    }
+}
+
+impl FromJson for Time {
+    fn from_json(val: &JsonValue) -> Option<Self> {
+        Time::parse(val.as_str()?).ok()
+    }
+}
+
+impl FromJson for DateTime {
+    fn from_json(val: &JsonValue) -> Option<Self> {
+        DateTime::parse(val.as_str()?).ok()
+    }
+}
+
+impl FromJson for Version {
+    fn from_json(val: &JsonValue) -> Option<Self> {
+        Version::parse(val.as_str()?).ok()
+    }
+}
+
+struct Validator {
+    violations: Vec<Violation>,
+    // a partial document over the defaults: nothing is required, readonly fields can't be set
+    overrides: bool,
+    // `must_exist` paths are checked against the local filesystem
+    check_paths: bool,
+}
+
+impl Validator {
+    fn report(&mut self, path: &str, fld: &FieldType, message: String) {
+        let pos = fld.position();
+        self.violations.push(Violation {
+            path: path.to_string(),
+            message,
+            position: if pos == (0, 0) { None } else { Some(pos) },
+        });
+    }
+
+    // arrays are checked item by item
+    fn check_items<F>(&mut self, is_array: bool, val: &JsonValue, path: &str, fld: &FieldType, mut check: F)
+        where F: FnMut(&mut Validator, &JsonValue, &str) {
+        if !is_array {
+            return check(self, val, path);
+        }
+        match val.as_array() {
+            Some(items) => {
+                for (id, item) in items.iter().enumerate() {
+                    check(self, item, &format!("{}/{}", path, id));
+                }
+            },
+            None => self.report(path, fld, format!("should be an array, found {}", val.type_name())),
+        }
+    }
+
+    fn check_number<T: Numeric + FromJson>(&mut self, num: &NumberType<T>, val: &JsonValue, path: &str, fld: &FieldType) {
+        self.check_items(num.value().is_array(), val, path, fld, |validator, item, at| {
+            let value = match T::from_json(item) {
+                Some(v) => v,
+                None => return validator.report(at, fld, format!("{} is not a valid {}", utils::shown(item), T::name())),
+            };
+            if !num.check_minmax(value.clone()) {
+                validator.report(at, fld, format!("{} is out of {}", value.to_string(), utils::interval_text(num.interval())));
+            }
+            if !num.check_step(value.clone()) {
+                let step = num.step().map(|v| v.to_string()).unwrap_or_default();
+                validator.report(at, fld, format!("{} is not a multiple of {}", value.to_string(), step));
+            }
+            if !num.check_enum(value.clone()) {
+                validator.report(at, fld, format!("{} is not one of the enum values", value.to_string()));
+            }
+        });
+    }
+
+    // strings, addresses, byte strings and paths
+    fn check_text<F>(&mut self, is_array: bool, val: &JsonValue, path: &str, fld: &FieldType, check: F)
+        where F: Fn(&str) -> Result<(), String> {
+        self.check_items(is_array, val, path, fld, |validator, item, at| {
+            match item.as_str() {
+                Some(text) => if let Err(msg) = check(text) {
+                    validator.report(at, fld, msg);
+                },
+                None => validator.report(at, fld, format!("should be a string, found {}", item.type_name())),
+            }
+        });
+    }
+
+    fn check_object(&mut self, obj: &ObjectType, val: &JsonValue, path: &str, fld: Option<&FieldType>) {
+        let fields = match val.as_object() {
+            Some(v) => v,
+            None => {
+                if let Some(fld) = fld {
+                    self.report(path, fld, format!("should be an object, found {}", val.type_name()));
+                }
+                return;
+            },
+        };
+        for (name, schema) in obj.fields().iter() {
+            let at = format!("{}/{}", path, pointer_token(name));
+            match fields.get(name) {
+                Some(_) if self.overrides && schema.options().has_bool("readonly") => {
+                    self.report(&at, schema, String::from("readonly field can't be overridden"))
+                },
+                Some(v) => self.check_field(schema, v, &at),
+                None if self.overrides => {},
+                None if schema.options().has_bool("required") => self.report(&at, schema, String::from("required field is missing")),
+                None => {},
+            }
+        }
+        for (name, _) in fields.iter() {
+            if !obj.has_field(name) {
+                let at = format!("{}/{}", path, pointer_token(name));
+                match fld {
+                    Some(fld) => self.report(&at, fld, String::from("unknown field")),
+                    None => self.violations.push(Violation { path: at, message: String::from("unknown field"), position: None }),
+                }
+            }
+        }
+    }
+
+    fn check_field(&mut self, fld: &FieldType, val: &JsonValue, path: &str) {
+        match fld.value() {
+            Element::None => {},
+            // merged objects are checked after the merge
+            Element::Any(_) if self.overrides && val.as_object().is_some() => {},
+            Element::Any(v) => if let Some(vals) = v.enum_values() {
+                if !vals.values().iter().any(|e| utils::same_value(e, val)) {
+                    self.report(path, fld, format!("{} is not one of the enum values", utils::shown(val)));
+                }
+            },
+            Element::Boolean(v) => self.check_items(v.value().is_array(), val, path, fld, |validator, item, at| {
+                if item.as_bool().is_none() {
+                    validator.report(at, fld, format!("should be a boolean, found {}", item.type_name()));
+                }
+            }),
+            Element::String(v) => self.check_text(v.value().is_array(), val, path, fld, |text| {
+                if v.check_enum(&text.to_string()) { Ok(()) } else { Err(format!("{} is not one of the enum values", utils::shown(&JsonValue::String(text.to_string())))) }
+            }),
+            Element::Net(v) => self.check_text(v.value().is_array(), val, path, fld, |text| {
+                let norm = v.kind().normalize(text)?;
+                if !v.check_networks(&norm) {
+                    Err(format!("{} is not within the allowed networks", norm))
+                } else if !v.check_enum(&norm) {
+                    Err(format!("{} is not one of the enum values", norm))
+                } else {
+                    Ok(())
+                }
+            }),
+            Element::Bytes(v) => self.check_text(v.value().is_array(), val, path, fld, |text| {
+                match v.encoding().decode(text) {
+                    Some(data) if v.check_length(data.len()) => Ok(()),
+                    Some(data) => Err(format!("length {} is out of {}", data.len(), utils::interval_text(v.length()))),
+                    None => Err(format!("{} is not valid {}", utils::shown(&JsonValue::String(text.to_string())), v.encoding().name())),
+                }
+            }),
+            Element::Path(v) => {
+                let check_paths = self.check_paths && v.must_exist();
+                self.check_text(v.value().is_array(), val, path, fld, |text| {
+                    v.check(text)?;
+                    if check_paths && !std::path::Path::new(text).exists() {
+                        return Err(format!("path '{}' does not exist", text));
+                    }
+                    Ok(())
+                })
+            },
+            Element::Integer(v) => self.check_number(v, val, path, fld),
+            Element::Unsigned(v) => self.check_number(v, val, path, fld),
+            Element::BigInt(v) => self.check_number(v, val, path, fld),
+            Element::Floating(v) => self.check_number(v, val, path, fld),
+            Element::Decimal(v) => self.check_number(v, val, path, fld),
+            Element::Duration(v) => self.check_number(v, val, path, fld),
+            Element::Size(v) => self.check_number(v, val, path, fld),
+            Element::Date(v) => self.check_number(v, val, path, fld),
+            Element::Time(v) => self.check_number(v, val, path, fld),
+            Element::DateTime(v) => self.check_number(v, val, path, fld),
+            Element::Semver(v) => self.check_number(v, val, path, fld),
+            Element::Object(v) => self.check_items(v.value().is_array(), val, path, fld, |validator, item, at| {
+                validator.check_object(v, item, at, Some(fld));
+                if let (Some(vals), Some(_), false) = (v.enum_values(), item.as_object(), validator.overrides) {
+                    if !vals.values().iter().any(|e| utils::same_object(e, item, Some(v))) {
+                        validator.report(at, fld, String::from("the object is not one of the enum values"));
+                    }
+                }
+            }),
+        }
+    }
+}
+
+impl Validator {
+    // enums of objects and `any` values are checked on the values merged with the overrides
+    fn check_merged(&mut self, fld: &FieldType, merged: &Element, val: &JsonValue, path: &str) {
+        let fields = match val.as_object() {
+            Some(v) => v,
+            None => return,
+        };
+        let format = Formatting::new(0).with_secrets(SecretMode::Show);
+        let merged_json = JsonValue::parse(&element_format(merged, &format)).unwrap_or(JsonValue::Null);
+        match (fld.value(), merged) {
+            (Element::Object(obj), Element::Object(res)) if !obj.value().is_array() => {
+                if let Some(vals) = obj.enum_values() {
+                    if !vals.values().iter().any(|e| utils::same_object(e, &merged_json, Some(obj))) {
+                        self.report(path, fld, String::from("the merged object is not one of the enum values"));
+                    }
+                }
+                for (name, v) in fields.iter() {
+                    if let (Some(schema), Some(next)) = (obj.get_field(name), res.value_fields().get(name)) {
+                        self.check_merged(schema, next.value(), v, &format!("{}/{}", path, pointer_token(name)));
+                    }
+                }
+            },
+            (Element::Any(any), Element::Any(_)) => if let Some(vals) = any.enum_values() {
+                if !vals.values().iter().any(|e| utils::same_value(e, &merged_json)) {
+                    self.report(path, fld, String::from("the merged value is not one of the enum values"));
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+fn check_document(module: &Module, val: &JsonValue, overrides: bool, check_paths: bool) -> Vec<Violation> {
+    let mut root = ObjectType::new();
+    for fld in module.fields() {
+        root.add_field(fld.clone());
+    }
+    let mut validator = Validator { violations: Vec::new(), overrides, check_paths };
+    if val.as_object().is_none() {
+        validator.violations.push(Violation {
+            path: String::new(),
+            message: format!("should be an object, found {}", val.type_name()),
+            position: None,
+        });
+    } else {
+        validator.check_object(&root, val, "", None);
+    }
+    validator.violations
+}
+
+// the document is an object with the fields of the module
+pub fn validate_module(module: &Module, val: &JsonValue, check_paths: bool) -> Vec<Violation> {
+    check_document(module, val, false, check_paths)
+}
+
+// the document changes some of the default values of the module
+pub fn validate_overrides(module: &Module, val: &JsonValue, check_paths: bool) -> Vec<Violation> {
+    check_document(module, val, true, check_paths)
+}
+
+// the merged module has the values of the checked override document
+pub fn validate_merged(module: &Module, merged: &Module, val: &JsonValue) -> Vec<Violation> {
+    let mut validator = Validator { violations: Vec::new(), overrides: true, check_paths: false };
+    if let Some(fields) = val.as_object() {
+        for (name, v) in fields.iter() {
+            if let (Some(fld), Some(res)) = (module.get_field(name), merged.get_field(name)) {
+                validator.check_merged(fld, res.value(), v, &format!("/{}", pointer_token(name)));
+            }
+        }
+    }
+    validator.violations
+}
+
+// the document is the value of the field
+pub fn validate_field(fld: &FieldType, val: &JsonValue, check_paths: bool) -> Vec<Violation> {
+    let mut validator = Validator { violations: Vec::new(), overrides: false, check_paths };
+    validator.check_field(fld, val, "");
+    validator.violations
+}